@@ -13,6 +13,62 @@ pub trait Alphabet {
     fn encode_to_buffer(self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError>;
 
     fn decode_to_buffer(self, input: &str, output: &mut [u8]) -> Result<usize, DecodeError>;
+
+    /// Returns the byte position of the first character of `input` not in the alphabet.
+    ///
+    /// The default implementation decodes each character on its own.
+    fn validate(self, input: &str) -> Result<(), usize>
+    where
+        Self: Copy,
+    {
+        let mut buffer = [0u8; 16];
+        for (i, c) in input.char_indices() {
+            if self.decode_to_buffer(&input[i..i + c.len_utf8()], &mut buffer).is_err() {
+                return Err(i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes an integer, see [`crate::encode_u128`].
+    ///
+    /// The default implementation encodes the big-endian bytes of `n` without leading zeros.
+    #[cfg(feature = "alloc")]
+    fn encode_u128(self, n: u128) -> Result<String, EncodeError>
+    where
+        Self: Sized,
+    {
+        let bytes = n.to_be_bytes();
+        // Zero still encodes as one symbol.
+        let start = (n.leading_zeros() / 8).min(15) as usize;
+        self.encode(&bytes[start..])
+    }
+
+    /// Decodes an integer, see [`crate::decode_u128`].
+    ///
+    /// The default implementation skips the leading zeroth symbols, decoding each character on
+    /// its own, and decodes the rest as at most 16 bytes.
+    fn decode_u128(self, input: &str) -> Result<u128, DecodeError>
+    where
+        Self: Copy,
+    {
+        if input.is_empty() {
+            return Err(DecodeError);
+        }
+        let mut buffer = [0u8; 16];
+        let mut start = input.len();
+        for (i, c) in input.char_indices() {
+            match self.decode_to_buffer(&input[i..i + c.len_utf8()], &mut buffer) {
+                Ok(1) if buffer[0] == 0 => (),
+                _ => {
+                    start = i;
+                    break;
+                }
+            }
+        }
+        let len = self.decode_to_buffer(&input[start..], &mut buffer)?;
+        Ok(buffer[..len].iter().fold(0, |n, &x| n << 8 | u128::from(x)))
+    }
 }
 
 impl Alphabet for &[u8] {
@@ -45,6 +101,25 @@ impl Alphabet for &[u8] {
     fn decode_to_buffer(self, input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
         U8Decoder::new(self).decode_to_buffer(input, output)
     }
+
+    fn validate(self, input: &str) -> Result<(), usize> {
+        U8Decoder::new(self).validate(input)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_u128(self, n: u128) -> Result<String, EncodeError> {
+        if !self.is_ascii() {
+            return Err(EncodeError::InvalidAlphabet);
+        }
+
+        let mut out = crate::encoder::encode_u128(self, n)?;
+        out.reverse();
+        Ok(String::from_utf8(out).expect("Alphabet checked ASCII; indices always within bounds; qed"))
+    }
+
+    fn decode_u128(self, input: &str) -> Result<u128, DecodeError> {
+        U8Decoder::new(self).decode_u128(input)
+    }
 }
 
 impl Alphabet for &str {
@@ -90,4 +165,82 @@ impl Alphabet for &str {
             Err(DecodeError)
         }
     }
+
+    fn validate(self, input: &str) -> Result<(), usize> {
+        if self.is_ascii() {
+            U8Decoder::new(self.as_bytes()).validate(input)
+        } else {
+            match input.char_indices().find(|&(_, c)| !self.contains(c)) {
+                Some((i, _)) => Err(i),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_u128(self, n: u128) -> Result<String, EncodeError> {
+        if self.is_ascii() {
+            let mut out = crate::encoder::encode_u128(self.as_bytes(), n)?;
+            out.reverse();
+            Ok(String::from_utf8(out).expect("Alphabet checked ASCII; indices always within bounds; qed"))
+        } else {
+            let alphabet: Vec<char> = self.chars().collect();
+            let out = crate::encoder::encode_u128(&alphabet, n)?;
+            Ok(out.iter().rev().collect())
+        }
+    }
+
+    fn decode_u128(self, input: &str) -> Result<u128, DecodeError> {
+        if self.is_ascii() {
+            U8Decoder::new(self.as_bytes()).decode_u128(input)
+        } else {
+            #[cfg(feature = "alloc")]
+            {
+                let alphabet: Vec<char> = self.chars().collect();
+                CharDecoder(&alphabet).decode_u128(input)
+            }
+            #[cfg(not(feature = "alloc"))]
+            Err(DecodeError)
+        }
+    }
+}
+
+impl Alphabet for &[char] {
+    #[inline(always)]
+    #[cfg(feature = "alloc")]
+    fn encode(self, input: &[u8]) -> Result<String, EncodeError> {
+        let out = crate::encoder::encode(self, input);
+        Ok(out.iter().rev().collect())
+    }
+
+    #[inline(always)]
+    #[cfg(feature = "alloc")]
+    fn decode(self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        CharDecoder(self).decode(input)
+    }
+
+    fn encode_to_buffer(self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+        crate::encoder::encode_to_buffer(self, input, output)
+    }
+
+    fn decode_to_buffer(self, input: &str, output: &mut [u8]) -> Result<usize, DecodeError> {
+        CharDecoder(self).decode_to_buffer(input, output)
+    }
+
+    fn validate(self, input: &str) -> Result<(), usize> {
+        match input.char_indices().find(|&(_, c)| !self.contains(&c)) {
+            Some((i, _)) => Err(i),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_u128(self, n: u128) -> Result<String, EncodeError> {
+        let out = crate::encoder::encode_u128(self, n)?;
+        Ok(out.iter().rev().collect())
+    }
+
+    fn decode_u128(self, input: &str) -> Result<u128, DecodeError> {
+        CharDecoder(self).decode_u128(input)
+    }
 }
@@ -106,6 +106,35 @@ impl<'a> BigUintView<'a> {
         true
     }
 
+    /// Load a `u128` into the last four chunks of the buffer.
+    #[cfg(feature = "alloc")]
+    pub fn load_u128(&mut self, n: u128) -> bool {
+        let len = self.chunks.len();
+        if len < 4 {
+            return false;
+        }
+        for x in &mut self.chunks[..len - 4] {
+            *x = 0;
+        }
+        for (i, chunk) in self.chunks[len - 4..].iter_mut().enumerate() {
+            *chunk = (n >> (96 - 32 * i)) as u32;
+        }
+        self.start = self.chunks.iter().position(|&x| x != 0).unwrap_or(len);
+        true
+    }
+
+    /// Read the value back as a `u128`, or `None` if it does not fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.chunks.len() - self.start > 4 {
+            return None;
+        }
+        Some(
+            self.chunks[self.start..]
+                .iter()
+                .fold(0u128, |acc, &x| (acc << 32) | u128::from(x)),
+        )
+    }
+
     /// Copie les octets dans un buffer de sortie.
     pub fn copy_into_bytes_be(&self, out: &mut [u8]) -> Result<usize, ()> {
         if self.is_zero() {
@@ -136,12 +165,46 @@ impl<'a> BigUintView<'a> {
     }
 }
 
+/// Buffer de chunks pour une [`BigUintView`].
+///
+/// Il contient la valeur en cours de conversion, qui peut être secrète : il est donc effacé à la
+/// destruction avec la feature `zeroize`, qu'il soit sur la pile ou sur le tas.
+pub struct Chunks<T: AsRef<[u32]> + AsMut<[u32]>>(pub T);
+
+impl<T: AsRef<[u32]> + AsMut<[u32]>> core::ops::Deref for Chunks<T> {
+    type Target = [u32];
+
+    fn deref(&self) -> &[u32] {
+        self.0.as_ref()
+    }
+}
+
+impl<T: AsRef<[u32]> + AsMut<[u32]>> core::ops::DerefMut for Chunks<T> {
+    fn deref_mut(&mut self) -> &mut [u32] {
+        self.0.as_mut()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: AsRef<[u32]> + AsMut<[u32]>> Drop for Chunks<T> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self.0.as_mut());
+    }
+}
+
 // Compatibilité descendante pour le mode alloc
 #[cfg(feature = "alloc")]
 pub struct BigUint {
     pub chunks: Vec<u32>,
 }
 
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+impl Drop for BigUint {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.chunks);
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl BigUint {
     pub fn with_capacity(capacity: usize) -> Self {
@@ -162,6 +225,7 @@ impl BigUint {
     pub fn mul_add(&mut self, multiplicator: u32, addition: u32) {
         let start = self.chunks.iter().position(|&x| x != 0).unwrap_or(self.chunks.len());
         if start == 0 && !self.is_zero() {
+            self.reserve_one();
             self.chunks.insert(0, 0);
             let mut view = BigUintView {
                 chunks: &mut self.chunks,
@@ -185,6 +249,16 @@ impl BigUint {
         self.chunks.iter().all(|&x| x == 0)
     }
 
+    /// Réserve un chunk de plus sans laisser de copie de la valeur dans la mémoire libérée.
+    fn reserve_one(&mut self) {
+        #[cfg(feature = "zeroize")]
+        if self.chunks.len() == self.chunks.capacity() {
+            let mut chunks = Vec::with_capacity(2 * self.chunks.len());
+            chunks.extend_from_slice(&self.chunks);
+            drop(Chunks(core::mem::replace(&mut self.chunks, chunks)));
+        }
+    }
+
     pub fn from_bytes_be(bytes: &[u8]) -> Self {
         let mut chunks = vec![0u32; bytes.len().div_ceil(4)];
         let mut view = BigUintView {
@@ -195,8 +269,8 @@ impl BigUint {
         BigUint { chunks }
     }
 
-    pub fn into_bytes_be(self) -> Vec<u8> {
-        let mut chunks = self.chunks;
+    pub fn into_bytes_be(mut self) -> Vec<u8> {
+        let mut chunks = Chunks(core::mem::take(&mut self.chunks));
         let chunks_len = chunks.len();
         let start = chunks.iter().position(|&x| x != 0).unwrap_or(chunks_len);
         let view = BigUintView {
@@ -3,7 +3,7 @@ use crate::Vec;
 
 #[cfg(feature = "alloc")]
 use crate::bigint::BigUint;
-use crate::bigint::BigUintView;
+use crate::bigint::{BigUintView, Chunks};
 use crate::DecodeError;
 
 #[allow(clippy::extra_unused_lifetimes)]
@@ -41,10 +41,12 @@ where
         let leaders = Self::iter(input).take_while(|byte| *byte == leader).count();
 
         let bytes = big.into_bytes_be();
+        #[cfg(feature = "zeroize")]
+        let bytes = zeroize::Zeroizing::new(bytes);
 
         let mut res = Vec::with_capacity(bytes.len() + leaders);
         res.resize(leaders, 0);
-        res.extend(bytes);
+        res.extend_from_slice(&bytes);
 
         Ok(res)
     }
@@ -58,7 +60,7 @@ where
 
         // On utilise un buffer fixe sur la pile pour le calcul intermédiaire.
         // 128 chunks = 512 octets de données binaires max.
-        let mut chunks = [0u32; 128];
+        let mut chunks = Chunks([0u32; 128]);
         let mut big = BigUintView::new(&mut chunks);
 
         for c in Self::iter(input) {
@@ -85,6 +87,26 @@ where
 
         Ok(written + leaders)
     }
+
+    fn decode_u128(&self, input: &'a str) -> Result<u128, DecodeError> {
+        if input.is_empty() {
+            return Err(DecodeError);
+        }
+        let base = self.alphabet().len() as u32;
+
+        let mut chunks = Chunks([0u32; 4]);
+        let mut big = BigUintView::new(&mut chunks);
+
+        for c in Self::iter(input) {
+            if let Some(carry) = self.carry(c) {
+                big.mul_add(base, carry).map_err(|_| DecodeError)?;
+            } else {
+                return Err(DecodeError);
+            }
+        }
+
+        big.to_u128().ok_or(DecodeError)
+    }
 }
 
 pub(crate) struct U8Decoder<'b> {
@@ -103,6 +125,15 @@ impl<'a> U8Decoder<'a> {
         }
         U8Decoder { alphabet, lookup }
     }
+
+    /// Checks every byte against the lookup table, without any big-integer arithmetic.
+    #[inline]
+    pub(crate) fn validate(&self, input: &str) -> Result<(), usize> {
+        match input.bytes().position(|c| self.carry(c).is_none()) {
+            Some(i) => Err(i),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<'a, 'b> Decoder<'a, 'b> for U8Decoder<'b> {
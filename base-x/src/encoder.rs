@@ -52,8 +52,81 @@ where
     out
 }
 
-pub(crate) fn encode_to_buffer(
-    alpha: &[u8],
+/// Encode an integer directly, least significant symbol first.
+///
+/// Fails if the alphabet has fewer than 2 symbols, which would never reduce `n`.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode_u128<T>(alpha: &[T], n: u128) -> Result<Vec<T>, crate::EncodeError>
+where
+    T: Copy,
+{
+    if alpha.len() < 2 {
+        return Err(crate::EncodeError::InvalidAlphabet);
+    }
+    let base = alpha.len() as u32;
+
+    let mut chunks = crate::bigint::Chunks([0u32; 4]);
+    let mut big = crate::bigint::BigUintView::new(&mut chunks);
+    big.load_u128(n);
+    let mut out = Vec::with_capacity(128 / (32 - base.leading_zeros()) as usize + 1);
+
+    let big_pow = 32 / (32 - base.leading_zeros());
+    let big_base = base.pow(big_pow);
+
+    'fast: loop {
+        let mut big_rem = big.div_mod(big_base);
+
+        if big.is_zero() {
+            loop {
+                let (result, remainder) = (big_rem / base, big_rem % base);
+                out.push(alpha[remainder as usize]);
+                big_rem = result;
+
+                if big_rem == 0 {
+                    break 'fast;
+                }
+            }
+        } else {
+            for _ in 0..big_pow {
+                let (result, remainder) = (big_rem / base, big_rem % base);
+                out.push(alpha[remainder as usize]);
+                big_rem = result;
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Symbol written to a byte buffer by [`encode_to_buffer`].
+pub(crate) trait Symbol: Copy {
+    /// Writes the bytes of the symbol in reverse order at the start of `output`.
+    ///
+    /// Returns the number of bytes written, or `None` if `output` is too small.
+    fn write_reversed(self, output: &mut [u8]) -> Option<usize>;
+}
+
+impl Symbol for u8 {
+    fn write_reversed(self, output: &mut [u8]) -> Option<usize> {
+        *output.first_mut()? = self;
+        Some(1)
+    }
+}
+
+impl Symbol for char {
+    fn write_reversed(self, output: &mut [u8]) -> Option<usize> {
+        let mut bytes = [0u8; 4];
+        let bytes = self.encode_utf8(&mut bytes).as_bytes();
+        let output = output.get_mut(..bytes.len())?;
+        output.copy_from_slice(bytes);
+        // The whole output is reversed at the end, which puts the bytes back in order.
+        output.reverse();
+        Some(bytes.len())
+    }
+}
+
+pub(crate) fn encode_to_buffer<T: Symbol>(
+    alpha: &[T],
     input: &[u8],
     output: &mut [u8],
 ) -> Result<usize, crate::EncodeError> {
@@ -64,7 +137,7 @@ pub(crate) fn encode_to_buffer(
     let base = alpha.len() as u32;
 
     // Stack buffer for BigUint computation (512 bytes capacity)
-    let mut chunks = [0u32; 128];
+    let mut chunks = crate::bigint::Chunks([0u32; 128]);
     let mut big = crate::bigint::BigUintView::new(&mut chunks);
     if !big.load_be_bytes(input) {
         return Err(crate::EncodeError::InputTooLarge);
@@ -80,11 +153,9 @@ pub(crate) fn encode_to_buffer(
         if big.is_zero() {
             loop {
                 let (result, remainder) = (big_rem / base, big_rem % base);
-                if out_idx >= output.len() {
-                    return Err(crate::EncodeError::BufferTooSmall);
-                }
-                output[out_idx] = alpha[remainder as usize];
-                out_idx += 1;
+                out_idx += alpha[remainder as usize]
+                    .write_reversed(&mut output[out_idx..])
+                    .ok_or(crate::EncodeError::BufferTooSmall)?;
                 big_rem = result;
 
                 if big_rem == 0 {
@@ -94,11 +165,9 @@ pub(crate) fn encode_to_buffer(
         } else {
             for _ in 0..big_pow {
                 let (result, remainder) = (big_rem / base, big_rem % base);
-                if out_idx >= output.len() {
-                    return Err(crate::EncodeError::BufferTooSmall);
-                }
-                output[out_idx] = alpha[remainder as usize];
-                out_idx += 1;
+                out_idx += alpha[remainder as usize]
+                    .write_reversed(&mut output[out_idx..])
+                    .ok_or(crate::EncodeError::BufferTooSmall)?;
                 big_rem = result;
             }
         }
@@ -108,11 +177,9 @@ pub(crate) fn encode_to_buffer(
     let leader = alpha[0];
     for &byte in input.iter().take(input.len() - 1) {
         if byte == 0 {
-            if out_idx >= output.len() {
-                return Err(crate::EncodeError::BufferTooSmall);
-            }
-            output[out_idx] = leader;
-            out_idx += 1;
+            out_idx += leader
+                .write_reversed(&mut output[out_idx..])
+                .ok_or(crate::EncodeError::BufferTooSmall)?;
         } else {
             break;
         }
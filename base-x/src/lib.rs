@@ -57,8 +57,7 @@ impl fmt::Display for DecodeError {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for DecodeError {}
+impl core::error::Error for DecodeError {}
 
 #[derive(Debug)]
 pub enum EncodeError {
@@ -77,8 +76,7 @@ impl fmt::Display for EncodeError {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for EncodeError {}
+impl core::error::Error for EncodeError {}
 
 /// Encode an input vector using the given alphabet into a provided buffer.
 pub fn encode_to_buffer<A: Alphabet>(
@@ -110,6 +108,87 @@ pub fn decode<A: Alphabet>(alphabet: A, input: &str) -> Result<Vec<u8>, DecodeEr
     alphabet.decode(input)
 }
 
+/// Encode an integer using the given alphabet.
+///
+/// This is the same as encoding its big-endian bytes without leading zeros, except that `0`
+/// encodes as the zeroth symbol of the alphabet.
+#[cfg(feature = "alloc")]
+pub fn encode_u128<A: Alphabet>(alphabet: A, n: u128) -> Result<String, EncodeError> {
+    alphabet.encode_u128(n)
+}
+
+/// Decode an input string using the given alphabet into an integer.
+///
+/// Leading zeroth symbols do not change the value. Fails if the input is empty or if the value
+/// does not fit in a `u128`.
+pub fn decode_u128<A: Alphabet + Copy>(alphabet: A, input: &str) -> Result<u128, DecodeError> {
+    alphabet.decode_u128(input)
+}
+
+/// Encode an input vector using the given alphabet with the symbol at index `leader` as zero.
+///
+/// The symbol at index `leader` has value zero and the following symbols, wrapping around to the
+/// start of the alphabet, have the following values. In particular, leading zero bytes encode as
+/// that symbol. A `leader` of `0` is the same as [`encode`]. Fails if `leader` is not an index of
+/// the alphabet.
+#[cfg(feature = "alloc")]
+pub fn encode_with_leader(
+    alphabet: &str,
+    leader: usize,
+    input: &[u8],
+) -> Result<String, EncodeError> {
+    let alphabet = rotate(alphabet, leader).ok_or(EncodeError::InvalidAlphabet)?;
+    alphabet[..].encode(input)
+}
+
+/// Decode an input string using the given alphabet with the symbol at index `leader` as zero.
+///
+/// This is the inverse of [`encode_with_leader`]: leading symbols at index `leader` decode as zero
+/// bytes. Fails if `leader` is not an index of the alphabet.
+#[cfg(feature = "alloc")]
+pub fn decode_with_leader(
+    alphabet: &str,
+    leader: usize,
+    input: &str,
+) -> Result<Vec<u8>, DecodeError> {
+    let alphabet = rotate(alphabet, leader).ok_or(DecodeError)?;
+    alphabet[..].decode(input)
+}
+
+/// Returns the symbols of `alphabet` starting at index `leader` and wrapping around.
+#[cfg(feature = "alloc")]
+fn rotate(alphabet: &str, leader: usize) -> Option<Vec<char>> {
+    let mut symbols: Vec<char> = alphabet.chars().collect();
+    if leader >= symbols.len() {
+        return None;
+    }
+    symbols.rotate_left(leader);
+    Some(symbols)
+}
+
+/// Decode many input strings in parallel using the given alphabet.
+///
+/// The results are in the same order as `inputs`.
+#[cfg(feature = "rayon")]
+pub fn decode_many<A: Alphabet + Sync + Copy>(
+    alphabet: A,
+    inputs: &[&str],
+) -> Vec<Result<Vec<u8>, DecodeError>> {
+    use rayon::prelude::*;
+    inputs.par_iter().map(|input| alphabet.decode(input)).collect()
+}
+
+/// Check that every character of an input string belongs to the given alphabet.
+pub fn is_valid<A: Alphabet + Copy>(alphabet: A, input: &str) -> bool {
+    alphabet.validate(input).is_ok()
+}
+
+/// Check an input string against the given alphabet, returning the byte position of the first
+/// invalid character.
+pub fn validate<A: Alphabet + Copy>(alphabet: A, input: &str) -> Result<(), usize> {
+    alphabet.validate(input)
+}
+
 #[cfg(all(test, feature = "alloc"))]
 mod test {
     use super::decode;
@@ -160,6 +239,28 @@ mod test {
         assert_eq!(decoded, &[0xff, 0x00, 0xff, 0x00]);
     }
 
+    #[test]
+    fn char_slice_alphabet() {
+        let alphabet: Vec<char> = "😐😀😂🚀".chars().collect();
+        let input = [0xde, 0xad, 0x00, 0xbe, 0xef];
+
+        let encoded = encode(&alphabet[..], &input).unwrap();
+        assert_eq!(encoded, encode("😐😀😂🚀", &input).unwrap());
+        assert_eq!(decode(&alphabet[..], &encoded).unwrap(), input);
+        assert_eq!(decode(&alphabet[..], "😐😐😀").unwrap(), [0, 0, 1]);
+
+        let mut buffer = [0u8; 128];
+        let len = crate::encode_to_buffer(&alphabet[..], &input, &mut buffer).unwrap();
+        assert_eq!(&buffer[..len], encoded.as_bytes());
+        assert!(crate::encode_to_buffer(&alphabet[..], &input, &mut buffer[..len - 1]).is_err());
+
+        let mut buffer = [0u8; 16];
+        let len = crate::decode_to_buffer(&alphabet[..], &encoded, &mut buffer).unwrap();
+        assert_eq!(&buffer[..len], input);
+        assert!(decode(&alphabet[..], "😀x").is_err());
+        assert_eq!(crate::validate(&alphabet[..], "😀x"), Err(4));
+    }
+
     #[test]
     fn compare_no_alloc_to_standard() {
         use crate::decode_to_buffer;
@@ -191,6 +292,153 @@ mod test {
         );
     }
 
+    #[test]
+    fn validate() {
+        use crate::{is_valid, validate};
+
+        let alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        let encoded = encode(alphabet, &[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        assert!(is_valid(alphabet, &encoded));
+        assert!(is_valid(alphabet.as_bytes(), &encoded));
+        assert!(is_valid(alphabet, ""));
+        assert!(!is_valid(alphabet, "3yMApq0"));
+        assert_eq!(validate(alphabet, "3yMApq0"), Err(6));
+        assert_eq!(validate(alphabet.as_bytes(), "Il"), Err(0));
+        assert_eq!(validate(alphabet, "2l"), Err(1));
+        assert_eq!(validate(alphabet, "2é"), Err(1));
+
+        let alphabet = "😐😀";
+        assert_eq!(validate(alphabet, "😀😐"), Ok(()));
+        assert_eq!(validate(alphabet, "😀x😐"), Err(4));
+
+        // Uses the default implementation.
+        assert!(is_valid(Hex, "00ff"));
+        assert_eq!(validate(Hex, "0fg0"), Err(2));
+        assert_eq!(validate(Hex, "0é"), Err(1));
+    }
+
+    /// Alphabet only implementing the required methods
+    #[derive(Clone, Copy)]
+    struct Hex;
+
+    impl crate::Alphabet for Hex {
+        fn encode(self, input: &[u8]) -> Result<String, crate::EncodeError> {
+            "0123456789abcdef".encode(input)
+        }
+
+        fn decode(self, input: &str) -> Result<Vec<u8>, crate::DecodeError> {
+            "0123456789abcdef".decode(input)
+        }
+
+        fn encode_to_buffer(
+            self,
+            input: &[u8],
+            output: &mut [u8],
+        ) -> Result<usize, crate::EncodeError> {
+            "0123456789abcdef".encode_to_buffer(input, output)
+        }
+
+        fn decode_to_buffer(
+            self,
+            input: &str,
+            output: &mut [u8],
+        ) -> Result<usize, crate::DecodeError> {
+            "0123456789abcdef".decode_to_buffer(input, output)
+        }
+
+    }
+
+    #[test]
+    fn u128_roundtrip() {
+        use crate::{decode_u128, encode_u128};
+
+        let base58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        let base62 = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+        for alphabet in [base58, base62] {
+            assert_eq!(encode_u128(alphabet, 0).unwrap(), &alphabet[..1]);
+            assert_eq!(encode_u128(alphabet, 1).unwrap(), &alphabet[1..2]);
+            for n in [0, 1, 57, 58, 61, 62, 1 << 64, u128::MAX - 1, u128::MAX] {
+                let encoded = encode_u128(alphabet, n).unwrap();
+                let trimmed = n.to_be_bytes();
+                let trimmed = &trimmed[trimmed.iter().position(|&b| b != 0).unwrap_or(15)..];
+                assert_eq!(encoded, encode(alphabet, trimmed).unwrap());
+                assert_eq!(decode_u128(alphabet, &encoded).unwrap(), n);
+                assert_eq!(decode_u128(alphabet.as_bytes(), &encoded).unwrap(), n);
+            }
+        }
+        assert_eq!(encode_u128(base62, u128::MAX).unwrap(), "7n42DGM5Tflk9n8mt7Fhc7");
+        assert_eq!(decode_u128(base58, "11112").unwrap(), 1);
+
+        let overflow = format!("{}1", encode_u128(base62, u128::MAX).unwrap());
+        assert!(decode_u128(base62, &overflow).is_err());
+        assert!(decode_u128(base62, "").is_err());
+        assert!(decode_u128(base58, "0").is_err());
+
+        let emoji = "😐😀😂🚀";
+        let chars: Vec<char> = emoji.chars().collect();
+        assert_eq!(encode_u128(emoji, 27).unwrap(), "😀😂🚀");
+        assert_eq!(encode_u128(&chars[..], 27).unwrap(), "😀😂🚀");
+        assert_eq!(decode_u128(emoji, "😀😂🚀").unwrap(), 27);
+        assert_eq!(decode_u128(&chars[..], "😐😀😂🚀").unwrap(), 27);
+
+        assert!(encode_u128("0", 1).is_err());
+        assert!(encode_u128(&['0'][..], 1).is_err());
+
+        // Uses the default implementation.
+        for n in [0, 1, 15, 16, 1 << 64, u128::MAX] {
+            let encoded = encode_u128(Hex, n).unwrap();
+            assert_eq!(encoded, format!("{n:x}"));
+            assert_eq!(decode_u128(Hex, &encoded).unwrap(), n);
+            assert_eq!(decode_u128(Hex, &format!("000{encoded}")).unwrap(), n);
+        }
+        assert!(decode_u128(Hex, "").is_err());
+        assert!(decode_u128(Hex, "0g").is_err());
+        assert!(decode_u128(Hex, &format!("1{:x}", u128::MAX)).is_err());
+    }
+
+    #[test]
+    fn with_leader() {
+        use crate::{decode_with_leader, encode_with_leader};
+
+        let alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        let input = [0, 0, 0xde, 0xad, 0xbe, 0xef];
+        let encoded = encode_with_leader(alphabet, 0, &input).unwrap();
+        assert_eq!(encoded, encode(alphabet, &input).unwrap());
+        assert_eq!(decode_with_leader(alphabet, 0, &encoded).unwrap(), input);
+
+        // The last symbol is zero and the first symbol is one.
+        let last = alphabet.len() - 1;
+        let encoded = encode_with_leader(alphabet, last, &input).unwrap();
+        assert!(encoded.starts_with("zz") && !encoded.starts_with("zzz"));
+        assert_eq!(decode_with_leader(alphabet, last, &encoded).unwrap(), input);
+        assert_eq!(encode_with_leader(alphabet, last, &[0, 0, 1]).unwrap(), "zz1");
+        assert_eq!(decode_with_leader(alphabet, last, "zz1").unwrap(), [0, 0, 1]);
+
+        let emoji = "😐😀😂🚀";
+        assert_eq!(encode_with_leader(emoji, 3, &[0, 5]).unwrap(), "🚀😐😐");
+        assert_eq!(decode_with_leader(emoji, 3, "🚀😐😐").unwrap(), [0, 5]);
+
+        assert!(encode_with_leader(alphabet, alphabet.len(), &input).is_err());
+        assert!(decode_with_leader(alphabet, alphabet.len(), "zz1").is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn decode_many() {
+        let alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        let encoded: Vec<String> = (0u32..200)
+            .map(|i| encode(alphabet, &i.to_be_bytes()).unwrap())
+            .collect();
+        let mut inputs: Vec<&str> = encoded.iter().map(String::as_str).collect();
+        inputs.extend(["", "3yMApq0", "Il"]);
+
+        let parallel = crate::decode_many(alphabet, &inputs);
+        assert_eq!(parallel.len(), inputs.len());
+        for (input, result) in inputs.iter().zip(parallel) {
+            assert_eq!(result.ok(), decode(alphabet, input).ok());
+        }
+    }
+
     #[cfg(feature = "alloc")]
     mod property_tests {
         use super::super::{decode, encode, decode_to_buffer, encode_to_buffer};
@@ -26,6 +26,8 @@ impl fmt::Display for Error {
     }
 }
 
+impl core::error::Error for Error {}
+
 /// The base256emoji alphabet consisting of 256 unique emojis.
 pub const ALPHABET: [char; 256] = [
     '🚀', '🪐', '☄', '🛰', '🌌', '🌑', '🌒', '🌓', '🌔', '🌕', '🌖', '🌗', '🌘', '🌍', '🌏', '🌎', '🐉', '☀', '💻', '🖥', '💾', '💿', '😂', '❤', '😍', '🤣', '😊', '🙏', '💕', '😭', '😘', '👍',
@@ -57,6 +59,19 @@ pub fn encode_to_buffer(input: &[u8], output: &mut [u8]) -> Result<usize, Error>
     Ok(total_len)
 }
 
+/// Encodes a fixed-size byte array into a fixed-size UTF-8 array.
+/// Returns the array and the number of bytes used, the rest being zeros.
+///
+/// Each emoji takes at most 4 bytes, so `M = 4 * N` always fits.
+///
+/// # Errors
+/// Returns `Error::BufferTooSmall` if `M` bytes cannot hold the encoded string.
+pub fn encode_array<const N: usize, const M: usize>(input: &[u8; N]) -> Result<([u8; M], usize), Error> {
+    let mut output = [0u8; M];
+    let len = encode_to_buffer(input, &mut output)?;
+    Ok((output, len))
+}
+
 /// Encodes a byte slice into a `String`.
 /// 
 /// Each byte is mapped to its corresponding emoji in the alphabet.
@@ -110,6 +125,35 @@ pub fn decode(input: &str) -> Result<Vec<u8>, Error> {
     Ok(output)
 }
 
+/// Bytes serialized as a base256emoji string.
+///
+/// ```rust
+/// # #[cfg(feature = "serde")] {
+/// use lyxal_base256emoji::Emoji;
+/// let json = serde_json::to_string(&Emoji(vec![0, 1])).unwrap();
+/// assert_eq!(json, "\"🚀🪐\"");
+/// assert_eq!(serde_json::from_str::<Emoji>(&json).unwrap(), Emoji(vec![0, 1]));
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Emoji(pub Vec<u8>);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Emoji {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode(&self.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Emoji {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        decode(&input).map(Emoji).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +205,38 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn roundtrip(input in any::<Vec<u8>>()) {
+                let json = serde_json::to_string(&Emoji(input.clone())).unwrap();
+                prop_assert_eq!(&json, &serde_json::to_string(&encode(&input)).unwrap());
+                prop_assert_eq!(serde_json::from_str::<Emoji>(&json).unwrap(), Emoji(input));
+            }
+        }
+
+        #[test]
+        fn invalid_character() {
+            let error = serde_json::from_str::<Emoji>("\"🚀x\"").unwrap_err();
+            assert!(error.to_string().contains("Character 'x' at index 1 is not in alphabet"));
+        }
+    }
+
+    #[test]
+    fn encode_array_fixed() {
+        let input = [0x00, 0x17, 0xff, 0x2e];
+        let (output, len) = encode_array::<4, 16>(&input).unwrap();
+        assert_eq!(core::str::from_utf8(&output[..len]).unwrap(), "🚀❤🥂☺");
+        assert_eq!(len, 4 + 3 + 4 + 3);
+        assert!(output[len..].iter().all(|&b| b == 0));
+        assert_eq!(encode_array::<4, 13>(&input), Err(Error::BufferTooSmall));
+        assert_eq!(encode_array::<0, 0>(&[]), Ok(([], 0)));
+    }
+
     #[test]
     fn test_buffer_api() {
         let input = b"SurrealDB";
@@ -1,6 +1,6 @@
 use crate::alphabet::{self, SIZE, SIZE_SIZE};
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::{vec, vec::Vec};
 
 use core::fmt::{Display, Formatter};
@@ -29,8 +29,20 @@ impl Display for DecodeError {
 	}
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for DecodeError {}
+impl core::error::Error for DecodeError {}
+
+/// Returns the decoded length of a base45 string of `len` characters.
+///
+/// This only looks at the length, so it can reject untrusted input before allocating.
+///
+/// # Errors
+/// Returns `DecodeError::InvalidLength` if `len` has a remainder of 1 when divided by 3.
+pub const fn validate_length(len: usize) -> Result<usize, DecodeError> {
+	if len % 3 == 1 {
+		return Err(DecodeError::InvalidLength);
+	}
+	Ok(len / 3 * 2 + len % 3 / 2)
+}
 
 /// Decodes a base45 encoded string into an existing buffer.
 /// Returns the number of bytes written to the output.
@@ -41,24 +53,15 @@ impl std::error::Error for DecodeError {}
 /// - Returns `DecodeError::OutOfRange` if a sequence decodes to a value exceeding the allowed range.
 /// - Returns `DecodeError::BufferTooSmall` if the output buffer is too small.
 pub fn decode_to_buffer(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
-	if input.is_empty() {
-		return Ok(0);
-	}
-
-	if input.len() % 3 == 1 {
-		return Err(DecodeError::InvalidLength);
-	}
-
-	let required_len = (input.len() / 3) * 2
-		+ if input.len() % 3 == 2 {
-			1
-		} else {
-			0
-		};
+	let required_len = validate_length(input.len())?;
 	if output.len() < required_len {
 		return Err(DecodeError::BufferTooSmall);
 	}
+	decode_checked_length(input, output)
+}
 
+/// Decodes `input`, whose length was already checked by [`validate_length`], into `output`.
+fn decode_checked_length(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
 	let mut out_idx = 0;
 	let (chunks, remainder) = input.as_chunks::<3>();
 
@@ -92,28 +95,33 @@ pub fn decode_to_buffer(input: &[u8], output: &mut [u8]) -> Result<usize, Decode
 	Ok(out_idx)
 }
 
+/// Decodes a fixed-size base45 encoded string into a fixed-size array.
+///
+/// The output length `M` is determined by the input length `N`: `N` must not be 1 more than a
+/// multiple of 3, and `M` must be `N / 3 * 2`, plus 1 if `N % 3 == 2`. For example, 3 characters
+/// decode to 2 bytes, 5 to 3, and 12 to 8. Other `(N, M)` pairs fail to compile.
+///
+/// # Errors
+/// - Returns `DecodeError::InvalidCharacter` if a character is not in the Base45 alphabet.
+/// - Returns `DecodeError::OutOfRange` if a sequence decodes to a value exceeding the allowed range.
+pub fn decode_array<const N: usize, const M: usize>(input: &[u8; N]) -> Result<[u8; M], DecodeError> {
+	const {
+		assert!(N % 3 != 1, "invalid base45 input length");
+		assert!(M == N / 3 * 2 + N % 3 / 2, "output length does not match input length");
+	}
+	let mut output = [0u8; M];
+	let _ = decode_to_buffer(input, &mut output)?;
+	Ok(output)
+}
+
 /// Decodes a base45 encoded string into a `Vec<u8>`.
 ///
 /// # Errors
 /// Returns a `DecodeError` if the input is not a valid base45 string.
+#[cfg(feature = "alloc")]
 pub fn decode(input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
 	let input = input.as_ref();
-	if input.is_empty() {
-		return Ok(Vec::new());
-	}
-
-	if input.len() % 3 == 1 {
-		return Err(DecodeError::InvalidLength);
-	}
-
-	let required_len = (input.len() / 3) * 2
-		+ if input.len() % 3 == 2 {
-			1
-		} else {
-			0
-		};
-	let mut output = vec![0u8; required_len];
-	let len = decode_to_buffer(input, &mut output)?;
-	output.truncate(len);
+	let mut output = vec![0u8; validate_length(input.len())?];
+	let _ = decode_checked_length(input, &mut output)?;
 	Ok(output)
 }
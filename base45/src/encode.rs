@@ -22,8 +22,7 @@ impl fmt::Display for EncodeError {
 	}
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for EncodeError {}
+impl core::error::Error for EncodeError {}
 
 #[inline(always)]
 fn ae(b: u8) -> u8 {
@@ -11,14 +11,18 @@ extern crate alloc;
 pub mod alphabet;
 mod decode;
 mod encode;
+#[cfg(feature = "std")]
+mod stream;
 
-pub use decode::{DecodeError, decode_to_buffer};
+pub use decode::{DecodeError, decode_array, decode_to_buffer, validate_length};
 pub use encode::{EncodeError, encode_to_buffer};
 
 #[cfg(feature = "alloc")]
 pub use decode::decode;
 #[cfg(feature = "alloc")]
 pub use encode::encode;
+#[cfg(feature = "std")]
+pub use stream::{decode_stream, encode_stream};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,71 @@
+use crate::{decode_to_buffer, encode_to_buffer};
+
+use std::io::{self, Read, Write};
+
+/// Size of the input chunks read at once. Multiple of both 2 and 3.
+const CHUNK: usize = 4098;
+
+/// Reads as much as possible into `buf`, returning 0 only at end of input.
+fn read_some(r: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+	loop {
+		match r.read(buf) {
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+			x => return x,
+		}
+	}
+}
+
+/// Encodes everything read from `r` and writes the base45 string to `w`.
+///
+/// Input is processed in 2-byte aligned chunks, so arbitrarily large inputs use a fixed amount of
+/// memory.
+///
+/// # Errors
+/// Returns any error from reading `r` or writing `w`.
+pub fn encode_stream(mut r: impl Read, mut w: impl Write) -> io::Result<()> {
+	let mut input = [0u8; CHUNK];
+	let mut output = [0u8; CHUNK / 2 * 3];
+	let mut len = 0;
+	loop {
+		let n = read_some(&mut r, &mut input[len..])?;
+		len += n;
+		// Keep an odd byte for the next read, unless this is the end.
+		let end = if n == 0 { len } else { len - len % 2 };
+		let written = encode_to_buffer(&input[..end], &mut output)
+			.map_err(io::Error::other)?;
+		w.write_all(&output[..written])?;
+		input.copy_within(end..len, 0);
+		len -= end;
+		if n == 0 {
+			return w.flush();
+		}
+	}
+}
+
+/// Decodes the base45 string read from `r` and writes the bytes to `w`.
+///
+/// Input is processed in 3-byte aligned chunks. The length and range checks of the final group are
+/// deferred until the end of the input.
+///
+/// # Errors
+/// Returns any error from reading `r` or writing `w`. Invalid input is reported with
+/// [`io::ErrorKind::InvalidData`] wrapping a [`DecodeError`](crate::DecodeError).
+pub fn decode_stream(mut r: impl Read, mut w: impl Write) -> io::Result<()> {
+	let mut input = [0u8; CHUNK];
+	let mut output = [0u8; CHUNK / 3 * 2];
+	let mut len = 0;
+	loop {
+		let n = read_some(&mut r, &mut input[len..])?;
+		len += n;
+		// Keep a partial group for the next read, unless this is the end.
+		let end = if n == 0 { len } else { len - len % 3 };
+		let written = decode_to_buffer(&input[..end], &mut output)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		w.write_all(&output[..written])?;
+		input.copy_within(end..len, 0);
+		len -= end;
+		if n == 0 {
+			return w.flush();
+		}
+	}
+}
@@ -133,3 +133,62 @@ fn decode_full_bytes() {
 	let s = decode("FGWFGWFGWFGW").unwrap();
 	assert_eq!(s, b"\xff\xff\xff\xff\xff\xff\xff\xff");
 }
+
+#[test]
+fn decode_array_fixed() {
+	assert_eq!(decode_array(b"FGWFGWFGWFGW"), Ok([0xff; 8]));
+	assert_eq!(decode_array(b"UJCLQE7W581"), Ok(*b"base-45"));
+	assert_eq!(decode_array(b""), Ok([]));
+	assert_eq!(decode_array::<12, 8>(b"FGWFGWFGWFGw"), Err(DecodeError::InvalidCharacter));
+	assert_eq!(decode_array::<3, 2>(b"GGW"), Err(DecodeError::OutOfRange));
+}
+
+#[test]
+fn validate_length_checks() {
+	assert_eq!(validate_length(0), Ok(0));
+	assert_eq!(validate_length(1), Err(DecodeError::InvalidLength));
+	assert_eq!(validate_length(4), Err(DecodeError::InvalidLength));
+	assert_eq!(validate_length(5), Ok(3));
+	assert_eq!(validate_length(6), Ok(4));
+	assert_eq!(decode("A"), Err(DecodeError::InvalidLength));
+	assert_eq!(decode("BB8A"), Err(DecodeError::InvalidLength));
+	assert_eq!(decode_to_buffer(b"BB8A", &mut []), Err(DecodeError::InvalidLength));
+	assert_eq!(decode_to_buffer(b"A", &mut [0; 4]), Err(DecodeError::InvalidLength));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_roundtrip() {
+	/// Reader returning at most 7 bytes at a time, to split groups across reads.
+	struct Trickle<'a>(&'a [u8]);
+	impl std::io::Read for Trickle<'_> {
+		fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+			let n = buf.len().min(self.0.len()).min(7);
+			buf[..n].copy_from_slice(&self.0[..n]);
+			self.0 = &self.0[n..];
+			Ok(n)
+		}
+	}
+
+	let data: Vec<u8> = (0..10_001u32).map(|i| (i * 7 + i / 13) as u8).collect();
+	let mut encoded = Vec::new();
+	encode_stream(&data[..], &mut encoded).unwrap();
+	assert_eq!(encoded, encode(&data).into_bytes());
+	let mut trickled = Vec::new();
+	encode_stream(Trickle(&data), &mut trickled).unwrap();
+	assert_eq!(trickled, encoded);
+
+	let mut decoded = Vec::new();
+	decode_stream(Trickle(&encoded), &mut decoded).unwrap();
+	assert_eq!(decoded, data);
+	decoded.clear();
+	decode_stream(&encoded[..], &mut decoded).unwrap();
+	assert_eq!(decoded, data);
+
+	let mut sink = Vec::new();
+	let err = decode_stream(&b"FGWFGWF"[..], &mut sink).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+	let err = decode_stream(Trickle(b"FGWFGWGW"), &mut sink).unwrap_err();
+	let err = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+	assert_eq!(*err, DecodeError::OutOfRange);
+}
@@ -168,6 +168,25 @@ fn bench_base64(c: &mut Criterion) {
 	group.finish();
 }
 
+fn bench_base16_permissive(c: &mut Criterion) {
+	let mut group = c.benchmark_group("base16_permissive");
+
+	let mut input_dec = [0u8; 4096];
+	for (i, x) in input_dec.iter_mut().enumerate() {
+		*x = b"0123456789ABCDEF"[i % 16];
+	}
+	let mut output_dec = [0u8; 2048];
+
+	group.throughput(Throughput::Bytes(input_dec.len() as u64));
+	group.bench_function("decode_upper", |b| {
+		b.iter(|| data_encoding::HEXUPPER.decode_mut(&input_dec, &mut output_dec))
+	});
+	group.bench_function("decode_upper_permissive", |b| {
+		b.iter(|| data_encoding::HEXUPPER_PERMISSIVE.decode_mut(&input_dec, &mut output_dec))
+	});
+	group.finish();
+}
+
 fn bench_dnscurve(c: &mut Criterion) {
 	let mut group = c.benchmark_group("dnscurve");
 
@@ -189,14 +208,109 @@ fn bench_dnscurve(c: &mut Criterion) {
 	group.finish();
 }
 
+fn bench_tiny(c: &mut Criterion) {
+	let mut group = c.benchmark_group("tiny");
+	let bases = [
+		("base16", data_encoding::HEXLOWER),
+		("base32", data_encoding::BASE32),
+		("base64", data_encoding::BASE64),
+	];
+
+	let input_enc = [0x5au8; 8];
+	let mut output_enc = [0u8; 16];
+	let mut output_dec = [0u8; 16];
+
+	for (name, base) in &bases {
+		let input_dec = base.encode(&input_enc);
+		let input_dec = input_dec.as_bytes();
+		let len = base.decode_len(input_dec.len()).unwrap();
+		group.bench_function(format!("{name}_encode"), |b| {
+			b.iter(|| base.encode_mut(&input_enc, &mut output_enc[..input_dec.len()]))
+		});
+		group.bench_function(format!("{name}_decode"), |b| {
+			b.iter(|| base.decode_mut(input_dec, &mut output_dec[..len]))
+		});
+	}
+	group.finish();
+}
+
+fn bench_base58_short(c: &mut Criterion) {
+	let mut group = c.benchmark_group("base58_short");
+	let base = &data_encoding::BASE58;
+	let prepared = base.prepare_arithmetic().unwrap();
+
+	let input_dec = base.encode(&[0x5au8; 32]);
+	let input_dec = input_dec.as_bytes();
+	let mut output_dec = [0u8; 64];
+
+	group.bench_function("decode", |b| b.iter(|| base.decode_mut(input_dec, &mut output_dec)));
+	group.bench_function("decode_prepared", |b| {
+		b.iter(|| prepared.decode_mut(input_dec, &mut output_dec))
+	});
+	group.finish();
+}
+
+fn bench_base58_blocks(c: &mut Criterion) {
+	let mut group = c.benchmark_group("base58_blocks");
+	let mut spec = data_encoding::BASE58.specification();
+	spec.arithmetic_block_size = 8;
+	let blocks = spec.encoding().unwrap();
+
+	let input_enc = [0x5au8; 4096];
+	let mut output_enc = [0u8; 8192];
+
+	group.throughput(Throughput::Bytes(input_enc.len() as u64));
+	group.bench_function("encode", |b| b.iter(|| blocks.encode_mut(&input_enc, &mut output_enc)));
+	group.bench_function("encode_whole", |b| {
+		b.iter(|| data_encoding::BASE58.encode_mut(&input_enc, &mut output_enc))
+	});
+
+	let input_dec = blocks.encode(&input_enc);
+	let input_dec = input_dec.as_bytes();
+	let mut output_dec = [0u8; 4096];
+
+	group.throughput(Throughput::Bytes(input_dec.len() as u64));
+	group.bench_function("decode", |b| b.iter(|| blocks.decode_mut(input_dec, &mut output_dec)));
+	group.finish();
+}
+
+#[cfg(feature = "unsafe-unchecked")]
+fn bench_base64_unchecked(c: &mut Criterion) {
+	let mut group = c.benchmark_group("base64_unchecked");
+
+	let mut input_dec = [0u8; 4096];
+	for (i, x) in input_dec.iter_mut().enumerate() {
+		*x = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"[i % 64];
+	}
+	let mut output_dec = [0u8; 3072];
+
+	group.throughput(Throughput::Bytes(input_dec.len() as u64));
+	group.bench_function("decode", |b| {
+		b.iter(|| data_encoding::BASE64.decode_mut(&input_dec, &mut output_dec))
+	});
+	group.bench_function("decode_unchecked", |b| {
+		b.iter(|| unsafe { data_encoding::BASE64.decode_mut_unchecked(&input_dec, &mut output_dec) })
+	});
+	group.finish();
+}
+
 criterion_group!(
 	benches,
 	bench_base02,
 	bench_base04,
 	bench_base08,
 	bench_base16,
+	bench_base16_permissive,
 	bench_base32,
 	bench_base64,
-	bench_dnscurve
+	bench_dnscurve,
+	bench_tiny,
+	bench_base58_short,
+	bench_base58_blocks
 );
+#[cfg(feature = "unsafe-unchecked")]
+criterion_group!(unchecked, bench_base64_unchecked);
+#[cfg(not(feature = "unsafe-unchecked"))]
 criterion_main!(benches);
+#[cfg(feature = "unsafe-unchecked")]
+criterion_main!(benches, unchecked);
@@ -8,17 +8,45 @@ use crate::DecodeError;
 use crate::DecodeKind;
 use crate::EncodeError;
 use crate::EncodeKind;
-use crate::bigint::BigUintView;
+use crate::Scratch;
+use crate::bigint::{BigUintView, Chunks};
 
-/// Maximum buffer size for big integer operations (128 chunks = 512 bytes)
-const MAX_BIGINT_BUFFER: usize = 128;
+/// Default stack buffer size for big integer operations (128 chunks = 512 bytes)
+pub(crate) const MAX_BIGINT_BUFFER: usize = 128;
 
 /// Encode input bytes using arithmetic encoding with the given alphabet.
 ///
+/// This function computes on a stack buffer of `N` chunks. Inputs too large
+/// for it are encoded on a heap buffer if `grow` is set (and the `alloc`
+/// feature is enabled), otherwise they fail with `Overflow`. Both buffers
+/// are cleared after use with the `zeroize` feature (see [`Chunks`]).
+pub(crate) fn encode_to_buffer<const N: usize>(
+	alphabet: &[u8],
+	input: &[u8],
+	output: &mut [u8],
+	grow: bool,
+) -> Result<usize, EncodeError> {
+	let needed = input.len().div_ceil(4);
+	if needed <= N {
+		return encode_with_chunks(&mut Chunks([0u32; N]), alphabet, input, output);
+	}
+	#[cfg(feature = "alloc")]
+	if grow {
+		return encode_with_chunks(&mut Chunks(alloc::vec![0u32; needed]), alphabet, input, output);
+	}
+	let _ = grow;
+	Err(EncodeError {
+		kind: EncodeKind::Overflow,
+	})
+}
+
+/// Encode input bytes using the given buffer for big integer computation.
+///
 /// This function writes the encoded result to the provided output buffer.
 /// Returns the number of bytes written to the output buffer, or an error
 /// if the buffer is too small or the input is too large.
-pub(crate) fn encode_to_buffer(
+fn encode_with_chunks(
+	chunks: &mut [u32],
 	alphabet: &[u8],
 	input: &[u8],
 	output: &mut [u8],
@@ -29,9 +57,7 @@ pub(crate) fn encode_to_buffer(
 
 	let base = alphabet.len() as u32;
 
-	// Stack buffer for BigUint computation (512 bytes capacity)
-	let mut chunks = [0u32; MAX_BIGINT_BUFFER];
-	let mut big = BigUintView::new(&mut chunks);
+	let mut big = BigUintView::new(chunks);
 
 	if !big.load_be_bytes(input) {
 		return Err(EncodeError {
@@ -99,46 +125,91 @@ pub(crate) fn encode_to_buffer(
 	Ok(out_idx)
 }
 
-/// Decode input string using arithmetic decoding with the given alphabet.
+/// Marks bytes outside the alphabet in a lookup table.
+pub(crate) const INVALID_INDEX: u8 = 0xFF;
+
+/// Build the lookup table from bytes to their value in a decoding table.
 ///
-/// This function writes the decoded result to the provided output buffer.
-/// Returns the number of bytes written to the output buffer, or an error
-/// if the input contains invalid characters or the output buffer is too small.
-pub(crate) fn decode_to_buffer(
+/// Every symbol of a value is kept, like the other case of case-insensitive
+/// encodings or translated symbols.
+pub(crate) fn build_lookup_values(values: &[u8; 128], base: usize) -> [u8; 256] {
+	let mut lookup = [INVALID_INDEX; 256];
+	for (byte, &value) in values.iter().enumerate() {
+		if usize::from(value) < base {
+			lookup[byte] = value;
+		}
+	}
+	lookup
+}
+
+/// Decode input bytes using a lookup table built by [`build_lookup_values`].
+///
+/// See [`with_decode_chunks`] for the buffers used.
+pub(crate) fn decode_with_lookup<const N: usize>(
 	alphabet: &[u8],
-	input: &str,
+	lookup: &[u8; 256],
+	input: &[u8],
 	output: &mut [u8],
+	grow: bool,
 ) -> Result<usize, DecodeError> {
-	if input.is_empty() {
-		return Ok(0);
-	}
-
-	let base = alphabet.len() as u32;
+	with_decode_chunks::<N, _>(input, grow, |chunks| {
+		decode_with_chunks(chunks, alphabet, lookup, input, output)
+	})
+}
 
-	// Build lookup table for alphabet characters
-	const INVALID_INDEX: u8 = 0xFF;
-	let mut lookup = [INVALID_INDEX; 256];
+/// Return the decoded length of input bytes, without decoding them.
+///
+/// This is the number of leading zero symbols plus the number of bytes of
+/// the value, so it needs the same big integer computation as decoding. See
+/// [`with_decode_chunks`] for the buffers used.
+pub(crate) fn decoded_len<const N: usize>(
+	alphabet: &[u8],
+	lookup: &[u8; 256],
+	input: &[u8],
+	grow: bool,
+) -> Result<usize, DecodeError> {
+	with_decode_chunks::<N, _>(input, grow, |chunks| {
+		let mut big = BigUintView::new(chunks);
+		load_symbols(&mut big, alphabet, lookup, input)?;
+		let leaders = input.iter().take_while(|&&byte| lookup[byte as usize] == 0).count();
+		Ok(leaders + big.byte_len())
+	})
+}
 
-	for (i, &byte) in alphabet.iter().enumerate() {
-		lookup[byte as usize] = i as u8;
+/// Run `f` on a buffer of chunks large enough for `input`.
+///
+/// This function tries a stack buffer of `N` chunks. Values too large for it
+/// are computed again on a heap buffer if `grow` is set (and the `alloc`
+/// feature is enabled), otherwise they fail with `Overflow`. Both buffers
+/// are cleared after use with the `zeroize` feature (see [`Chunks`]).
+pub(crate) fn with_decode_chunks<const N: usize, T>(
+	input: &[u8],
+	grow: bool,
+	mut f: impl FnMut(&mut [u32]) -> Result<T, DecodeError>,
+) -> Result<T, DecodeError> {
+	let result = f(&mut Chunks([0u32; N]));
+	#[cfg(feature = "alloc")]
+	if grow && result.as_ref().is_err_and(|error| error.kind == DecodeKind::Overflow) {
+		// Each symbol adds at most 8 bits, so this never overflows.
+		return f(&mut Chunks(alloc::vec![0u32; input.len().div_ceil(4)]));
 	}
+	let _ = (input, grow);
+	result
+}
 
-	// On utilise un buffer fixe sur la pile pour le calcul intermédiaire.
-	// 128 chunks = 512 octets de données binaires max.
-	let mut chunks = [0u32; MAX_BIGINT_BUFFER];
-	let mut big = BigUintView::new(&mut chunks);
-
-	for byte in input.bytes() {
+/// Load the value of input bytes in a big integer.
+///
+/// Positions are the number of symbols consumed before the error.
+fn load_symbols(
+	big: &mut BigUintView<'_>,
+	alphabet: &[u8],
+	lookup: &[u8; 256],
+	input: &[u8],
+) -> Result<(), DecodeError> {
+	let base = alphabet.len() as u32;
+	for (position, &byte) in input.iter().enumerate() {
 		match lookup[byte as usize] {
 			INVALID_INDEX => {
-				// Find the position of the invalid character
-				let position = input
-					.bytes()
-					.enumerate()
-					.find(|&(_, b)| b == byte)
-					.map(|(pos, _)| pos)
-					.unwrap_or(0);
-
 				return Err(DecodeError {
 					position,
 					kind: DecodeKind::Symbol,
@@ -147,21 +218,41 @@ pub(crate) fn decode_to_buffer(
 			index => {
 				if let Err(()) = big.mul_add(base, index as u32) {
 					return Err(DecodeError {
-						position: 0, // Position isn't meaningful for overflow
+						position,
 						kind: DecodeKind::Overflow,
 					});
 				}
 			}
 		}
 	}
+	Ok(())
+}
+
+/// Decode input bytes using the given buffer for big integer computation.
+///
+/// This function writes the decoded result to the provided output buffer.
+/// Returns the number of bytes written to the output buffer, or an error
+/// if the input contains invalid characters or the output buffer is too small.
+fn decode_with_chunks(
+	chunks: &mut [u32],
+	alphabet: &[u8],
+	lookup: &[u8; 256],
+	input: &[u8],
+	output: &mut [u8],
+) -> Result<usize, DecodeError> {
+	if input.is_empty() {
+		return Ok(0);
+	}
+
+	let mut big = BigUintView::new(chunks);
+	load_symbols(&mut big, alphabet, lookup, input)?;
 
 	let written = big.copy_into_bytes_be(output).map_err(|_| DecodeError {
 		position: 0, // Position isn't meaningful for buffer too small
 		kind: DecodeKind::BufferTooSmall,
 	})?;
 
-	let leader = alphabet[0];
-	let leaders = input.bytes().take_while(|&byte| byte == leader).count();
+	let leaders = input.iter().take_while(|&&byte| lookup[byte as usize] == 0).count();
 
 	if leaders > 0 {
 		if output.len() < written + leaders {
@@ -178,6 +269,222 @@ pub(crate) fn decode_to_buffer(
 	Ok(written + leaders)
 }
 
+/// Maximum number of bytes of a value of `len` symbols.
+///
+/// This is about `len * log(base) / log(256)`, rounded up per group of
+/// symbols. The group size is the one wasting the fewest bits among those
+/// whose largest value fits in a `u128`, so the bound is within a byte per
+/// group of the exact one.
+pub(crate) fn value_len(base: usize, len: usize) -> usize {
+	debug_assert!(base >= 2);
+	let base = base as u128;
+	// Number of bytes of the largest value below `power`.
+	let bytes = |power: u128| (128 - (power - 1).leading_zeros()).div_ceil(8) as usize;
+	let (mut symbols, mut power) = (1, base);
+	let mut group = (symbols, bytes(power));
+	while let Some(next) = power.checked_mul(base) {
+		(symbols, power) = (symbols + 1, next);
+		if bytes(power) * group.0 < group.1 * symbols {
+			group = (symbols, bytes(power));
+		}
+	}
+	let rest = (0..len % group.0).fold(1, |power, _| power * base);
+	len / group.0 * group.1 + bytes(rest)
+}
+
+/// Maximum number of bytes in a block of the block mode.
+///
+/// A block is computed as a `u128`, without big integers.
+pub(crate) const MAX_BLOCK_SIZE: usize = 16;
+
+/// Configuration of the block mode.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Blocks {
+	/// Number of bytes per block, from 1 to [`MAX_BLOCK_SIZE`].
+	pub(crate) size: usize,
+	/// Whether the last block is padded with zeros and its encoding truncated.
+	pub(crate) pad_last: bool,
+	/// Symbol abbreviating a whole block of zeros.
+	pub(crate) zero: Option<u8>,
+}
+
+impl Blocks {
+	/// Number of symbols encoding a block of `len` bytes.
+	///
+	/// A truncated last block keeps enough symbols for the dropped ones to
+	/// be worth at most one unit of the kept bytes, so decoding is exact.
+	pub(crate) fn symbols(self, base: usize, len: usize) -> usize {
+		if len == self.size || !self.pad_last {
+			return block_symbols(base, len);
+		}
+		let padding = 1u128 << (8 * (self.size - len));
+		let mut dropped = 0;
+		let mut weight = base as u128;
+		while weight <= padding {
+			weight *= base as u128;
+			dropped += 1;
+		}
+		block_symbols(base, self.size) - dropped
+	}
+
+	/// Number of bytes of a block encoded with `symbols` symbols.
+	///
+	/// This is the inverse of [`Blocks::symbols`]. Each additional byte needs
+	/// at least one more symbol, so the length is unique if it exists.
+	pub(crate) fn bytes(self, base: usize, symbols: usize) -> Option<usize> {
+		(1..=self.size).find(|&len| self.symbols(base, len) == symbols)
+	}
+}
+
+/// Number of symbols encoding a block of `len` bytes as a number.
+///
+/// This is the number of digits of the largest value of `len` bytes.
+pub(crate) fn block_symbols(base: usize, len: usize) -> usize {
+	debug_assert!(base >= 2 && (1..=MAX_BLOCK_SIZE).contains(&len));
+	let mut value = u128::MAX >> (128 - 8 * len);
+	let mut symbols = 0;
+	while value > 0 {
+		value /= base as u128;
+		symbols += 1;
+	}
+	symbols
+}
+
+/// Encode input bytes in independent blocks.
+///
+/// Each block is written as a fixed number of symbols, given by
+/// [`Blocks::symbols`], so the cost is linear in the input length. The last
+/// block may be shorter and is either encoded as a shorter number or padded
+/// with zeros and truncated.
+pub(crate) fn encode_blocks(
+	alphabet: &[u8],
+	blocks: Blocks,
+	input: &[u8],
+	output: &mut [u8],
+) -> Result<usize, EncodeError> {
+	let base = alphabet.len();
+	let full = block_symbols(base, blocks.size);
+	let buffer_too_small = EncodeError {
+		kind: EncodeKind::BufferTooSmall,
+	};
+	let mut out_idx = 0;
+	for chunk in input.chunks(blocks.size) {
+		let zero = blocks.zero.filter(|_| chunk.len() == blocks.size);
+		if let Some(zero) = zero.filter(|_| chunk.iter().all(|&byte| byte == 0)) {
+			*output.get_mut(out_idx).ok_or(buffer_too_small)? = zero;
+			out_idx += 1;
+			continue;
+		}
+		let len = blocks.symbols(base, chunk.len());
+		let output = output.get_mut(out_idx..out_idx + len).ok_or(buffer_too_small)?;
+		let mut value =
+			Scratch(chunk.iter().fold(0u128, |value, &byte| (value << 8) | u128::from(byte)));
+		let mut digits = len;
+		if blocks.pad_last && chunk.len() < blocks.size {
+			*value <<= 8 * (blocks.size - chunk.len());
+			digits = full;
+		}
+		// Truncated blocks only write their first symbols.
+		for i in (0..digits).rev() {
+			if i < len {
+				output[i] = alphabet[(*value % base as u128) as usize];
+			}
+			*value /= base as u128;
+		}
+		out_idx += len;
+	}
+	Ok(out_idx)
+}
+
+/// Decode input bytes in independent blocks.
+///
+/// This is the inverse of [`encode_blocks`]. The last group of symbols must
+/// have the length of an encoded block, and each group must decode to a
+/// value fitting in its block.
+pub(crate) fn decode_blocks(
+	alphabet: &[u8],
+	lookup: &[u8; 256],
+	blocks: Blocks,
+	input: &[u8],
+	output: &mut [u8],
+) -> Result<usize, DecodeError> {
+	let base = alphabet.len();
+	let full = block_symbols(base, blocks.size);
+	let error = |position, kind| DecodeError { position, kind };
+	let mut position = 0;
+	let mut out_idx = 0;
+	while position < input.len() {
+		if blocks.zero == Some(input[position]) {
+			let output = output.get_mut(out_idx..out_idx + blocks.size);
+			output.ok_or(error(position, DecodeKind::BufferTooSmall))?.fill(0);
+			out_idx += blocks.size;
+			position += 1;
+			continue;
+		}
+		let chunk = &input[position..core::cmp::min(position + full, input.len())];
+		let len = blocks.bytes(base, chunk.len()).ok_or(error(position, DecodeKind::Length))?;
+		// Truncated blocks are padded with the largest symbol.
+		let width = if blocks.pad_last { blocks.size } else { len };
+		let digits = if blocks.pad_last { full } else { chunk.len() };
+		let mut value = Scratch(0u128);
+		for offset in 0..digits {
+			let digit = match chunk.get(offset) {
+				None => base as u128 - 1,
+				Some(&byte) => match lookup[byte as usize] {
+					INVALID_INDEX => return Err(error(position + offset, DecodeKind::Symbol)),
+					digit => u128::from(digit),
+				},
+			};
+			*value = value
+				.checked_mul(base as u128)
+				.and_then(|value| value.checked_add(digit))
+				.ok_or(error(position, DecodeKind::Overflow))?;
+		}
+		if width < MAX_BLOCK_SIZE && *value >> (8 * width) != 0 {
+			return Err(error(position, DecodeKind::Overflow));
+		}
+		let output = output.get_mut(out_idx..out_idx + len);
+		let output = output.ok_or(error(position, DecodeKind::BufferTooSmall))?;
+		let bytes = Scratch(value.to_be_bytes());
+		output.copy_from_slice(&bytes[MAX_BLOCK_SIZE - width..][..len]);
+		out_idx += len;
+		position += chunk.len();
+	}
+	Ok(out_idx)
+}
+
+/// Return the decoded length of input bytes in independent blocks.
+///
+/// This checks the symbols and the length of the last group like
+/// [`decode_blocks`], but not the values.
+pub(crate) fn decoded_blocks_len(
+	alphabet: &[u8],
+	lookup: &[u8; 256],
+	blocks: Blocks,
+	input: &[u8],
+) -> Result<usize, DecodeError> {
+	let base = alphabet.len();
+	let full = block_symbols(base, blocks.size);
+	let error = |position, kind| DecodeError { position, kind };
+	let mut position = 0;
+	let mut len = 0;
+	while position < input.len() {
+		if blocks.zero == Some(input[position]) {
+			len += blocks.size;
+			position += 1;
+			continue;
+		}
+		let chunk = &input[position..core::cmp::min(position + full, input.len())];
+		let invalid = chunk.iter().position(|&byte| lookup[byte as usize] == INVALID_INDEX);
+		if let Some(offset) = invalid {
+			return Err(error(position + offset, DecodeKind::Symbol));
+		}
+		len += blocks.bytes(base, chunk.len()).ok_or(error(position, DecodeKind::Length))?;
+		position += chunk.len();
+	}
+	Ok(len)
+}
+
 #[cfg(feature = "alloc")]
 mod alloc_impl {
 	use super::*;
@@ -195,7 +502,7 @@ mod alloc_impl {
 		let base = alphabet.len() as u32;
 
 		// Stack buffer for BigUint computation (512 bytes capacity)
-		let mut chunks = [0u32; MAX_BIGINT_BUFFER];
+		let mut chunks = Chunks([0u32; MAX_BIGINT_BUFFER]);
 		let mut big = BigUintView::new(&mut chunks);
 
 		if !big.load_be_bytes(input) {
@@ -244,21 +551,17 @@ mod alloc_impl {
 	/// Decode input string using arithmetic decoding with the given alphabet.
 	///
 	/// This function returns the decoded result as a Vec<u8>.
-	pub(crate) fn decode(alphabet: &[u8], input: &str) -> Result<Vec<u8>, DecodeError> {
+	pub(crate) fn decode(
+		alphabet: &[u8],
+		lookup: &[u8; 256],
+		input: &str,
+	) -> Result<Vec<u8>, DecodeError> {
 		if input.is_empty() {
 			return Ok(Vec::new());
 		}
 
 		let base = alphabet.len() as u32;
 
-		// Build lookup table for alphabet characters
-		const INVALID_INDEX: u8 = 0xFF;
-		let mut lookup = [INVALID_INDEX; 256];
-
-		for (i, &byte) in alphabet.iter().enumerate() {
-			lookup[byte as usize] = i as u8;
-		}
-
 		// Use heap-allocated BigUint for potentially larger computations
 		let mut big = crate::bigint::BigUint::with_capacity(4);
 
@@ -278,8 +581,7 @@ mod alloc_impl {
 
 		let bytes = big.into_bytes_be();
 
-		let leader = alphabet[0];
-		let leaders = input.bytes().take_while(|&byte| byte == leader).count();
+		let leaders = input.bytes().take_while(|&byte| lookup[byte as usize] == 0).count();
 
 		let mut res = Vec::with_capacity(bytes.len() + leaders);
 		res.resize(leaders, 0);
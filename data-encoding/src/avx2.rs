@@ -0,0 +1,231 @@
+//! AVX2 kernels for hexadecimal and base64 with most significant bit first.
+//!
+//! These kernels are compiled for all x86 targets and selected at runtime with [`available`], so
+//! a binary built for the baseline target still uses them on CPUs supporting AVX2. Symbols and
+//! values are mapped with `vpshufb` on 16-byte slices of the tables, which works for any
+//! alphabet, instead of per-byte table lookups.
+//!
+//! Kernels only handle whole vectors and return how much input they processed. The rest is left
+//! to the scalar code, which is also the one reporting errors.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86 as x86_simd;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64 as x86_simd;
+
+use x86_simd::__m256i;
+
+/// Whether the CPU supports AVX2
+///
+/// Without the `std` feature, there is no runtime detection and this is only true when compiled
+/// with the `avx2` target feature.
+pub(crate) fn available() -> bool {
+	#[cfg(feature = "std")]
+	{
+		std::is_x86_feature_detected!("avx2")
+	}
+	#[cfg(not(feature = "std"))]
+	{
+		cfg!(target_feature = "avx2")
+	}
+}
+
+/// Returns the 16 bytes of `table` starting at `offset` in both lanes
+#[target_feature(enable = "avx2")]
+unsafe fn broadcast(table: &[u8], offset: usize) -> __m256i {
+	let table = &table[offset..offset + 16];
+	unsafe {
+		x86_simd::_mm256_broadcastsi128_si256(x86_simd::_mm_loadu_si128(table.as_ptr().cast()))
+	}
+}
+
+/// Encodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 32 bytes. The caller must check [`available`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn encode_hex(input: &[u8], output: &mut [u8], sym: &[u8; 256]) -> usize {
+	let n = core::cmp::min(input.len() / 32, output.len() / 64);
+	unsafe {
+		let table = broadcast(sym, 0);
+		let low_mask = x86_simd::_mm256_set1_epi8(0x0f);
+		for i in 0..n {
+			let x = x86_simd::_mm256_loadu_si256(input[32 * i..].as_ptr().cast());
+			let low = x86_simd::_mm256_and_si256(x, low_mask);
+			let high = x86_simd::_mm256_and_si256(x86_simd::_mm256_srli_epi16(x, 4), low_mask);
+			let low = x86_simd::_mm256_shuffle_epi8(table, low);
+			let high = x86_simd::_mm256_shuffle_epi8(table, high);
+			// Interleaving works within lanes, so the lanes are reordered when storing.
+			let a = x86_simd::_mm256_unpacklo_epi8(high, low);
+			let b = x86_simd::_mm256_unpackhi_epi8(high, low);
+			let output = &mut output[64 * i..64 * i + 64];
+			x86_simd::_mm256_storeu_si256(
+				output.as_mut_ptr().cast(),
+				x86_simd::_mm256_permute2x128_si256(a, b, 0x20),
+			);
+			x86_simd::_mm256_storeu_si256(
+				output[32..].as_mut_ptr().cast(),
+				x86_simd::_mm256_permute2x128_si256(a, b, 0x31),
+			);
+		}
+	}
+	n * 32
+}
+
+/// Encodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 24 bytes. Each lane loads 16 bytes for 12, so the prefix stops 4
+/// bytes before the end of `input`. The caller must check [`available`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn encode_base64(input: &[u8], output: &mut [u8], sym: &[u8; 256]) -> usize {
+	let n = core::cmp::min(input.len().saturating_sub(4) / 24, output.len() / 32);
+	unsafe {
+		let tables = [broadcast(sym, 0), broadcast(sym, 16), broadcast(sym, 32), broadcast(sym, 48)];
+		// Each group of 3 bytes [a, b, c] becomes the 32-bit word [b, a, c, b].
+		let spread = x86_simd::_mm256_setr_epi8(
+			1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10, 1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7,
+			10, 9, 11, 10,
+		);
+		for i in 0..n {
+			let input = &input[24 * i..24 * i + 28];
+			let low = x86_simd::_mm_loadu_si128(input.as_ptr().cast());
+			let high = x86_simd::_mm_loadu_si128(input[12..].as_ptr().cast());
+			let x = x86_simd::_mm256_set_m128i(high, low);
+			let x = x86_simd::_mm256_shuffle_epi8(x, spread);
+			// Moves each 6-bit value to its own byte, in output order.
+			let t0 = x86_simd::_mm256_and_si256(x, x86_simd::_mm256_set1_epi32(0x0fc0_fc00));
+			let t1 = x86_simd::_mm256_mulhi_epu16(t0, x86_simd::_mm256_set1_epi32(0x0400_0040));
+			let t2 = x86_simd::_mm256_and_si256(x, x86_simd::_mm256_set1_epi32(0x003f_03f0));
+			let t3 = x86_simd::_mm256_mullo_epi16(t2, x86_simd::_mm256_set1_epi32(0x0100_0010));
+			let index = x86_simd::_mm256_or_si256(t1, t3);
+			let mut y = x86_simd::_mm256_shuffle_epi8(tables[0], index);
+			for (&table, last) in tables[1..].iter().zip([15, 31, 47]) {
+				let select = x86_simd::_mm256_cmpgt_epi8(index, x86_simd::_mm256_set1_epi8(last));
+				let z = x86_simd::_mm256_shuffle_epi8(table, index);
+				y = x86_simd::_mm256_blendv_epi8(y, z, select);
+			}
+			x86_simd::_mm256_storeu_si256(output[32 * i..32 * i + 32].as_mut_ptr().cast(), y);
+		}
+	}
+	n * 24
+}
+
+/// Maps symbols to their values
+///
+/// Non-ASCII bytes and non-symbols (invalid, ignored, or padding) have their high bit set.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn map(x: __m256i, tables: &[__m256i; 8]) -> __m256i {
+	// Those intrinsics are only safe in AVX2 functions since Rust 1.87.
+	#[allow(unused_unsafe)]
+	unsafe {
+		let high = x86_simd::_mm256_and_si256(
+			x86_simd::_mm256_srli_epi16(x, 4),
+			x86_simd::_mm256_set1_epi8(0x0f),
+		);
+		// Non-ASCII bytes select no table and are kept as is.
+		let mut y = x;
+		for (k, &table) in (0..).zip(tables) {
+			let select = x86_simd::_mm256_cmpeq_epi8(high, x86_simd::_mm256_set1_epi8(k));
+			let z = x86_simd::_mm256_shuffle_epi8(table, x);
+			y = x86_simd::_mm256_blendv_epi8(y, z, select);
+		}
+		y
+	}
+}
+
+/// Returns the value table in 8 slices of 16 bytes
+#[target_feature(enable = "avx2")]
+unsafe fn val_tables(val: &[u8; 128]) -> [__m256i; 8] {
+	unsafe {
+		[
+			broadcast(val, 0),
+			broadcast(val, 16),
+			broadcast(val, 32),
+			broadcast(val, 48),
+			broadcast(val, 64),
+			broadcast(val, 80),
+			broadcast(val, 96),
+			broadcast(val, 112),
+		]
+	}
+}
+
+/// Returns the length of the prefix of `input` made of symbols
+///
+/// The prefix is a multiple of 32 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn symbols(input: &[u8], val: &[u8; 128]) -> usize {
+	let n = input.len() / 32;
+	unsafe {
+		let tables = val_tables(val);
+		for i in 0..n {
+			let x = map(x86_simd::_mm256_loadu_si256(input[32 * i..].as_ptr().cast()), &tables);
+			if x86_simd::_mm256_movemask_epi8(x) != 0 {
+				return 32 * i;
+			}
+		}
+	}
+	32 * n
+}
+
+/// Decodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 64 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn decode_hex(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> usize {
+	let n = core::cmp::min(input.len() / 64, output.len() / 32);
+	unsafe {
+		let tables = val_tables(val);
+		// Each pair of values [h, l] becomes h * 16 + l.
+		let weights = x86_simd::_mm256_set1_epi16(0x0110);
+		for i in 0..n {
+			let input = &input[64 * i..64 * i + 64];
+			let a = map(x86_simd::_mm256_loadu_si256(input.as_ptr().cast()), &tables);
+			let b = map(x86_simd::_mm256_loadu_si256(input[32..].as_ptr().cast()), &tables);
+			if x86_simd::_mm256_movemask_epi8(x86_simd::_mm256_or_si256(a, b)) != 0 {
+				return 64 * i;
+			}
+			let a = x86_simd::_mm256_maddubs_epi16(a, weights);
+			let b = x86_simd::_mm256_maddubs_epi16(b, weights);
+			// Packing works within lanes, so the lanes are reordered.
+			let y = x86_simd::_mm256_permute4x64_epi64(x86_simd::_mm256_packus_epi16(a, b), 0xd8);
+			x86_simd::_mm256_storeu_si256(output[32 * i..32 * i + 32].as_mut_ptr().cast(), y);
+		}
+	}
+	64 * n
+}
+
+/// Decodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 32 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn decode_base64(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> usize {
+	let n = core::cmp::min(input.len() / 32, output.len() / 24);
+	unsafe {
+		let tables = val_tables(val);
+		// Each group of 4 bytes becomes a 24-bit big-endian number, whose bytes are gathered
+		// first in each lane then first in the vector.
+		let gather = x86_simd::_mm256_setr_epi8(
+			2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10, 9, 8, 14,
+			13, 12, -1, -1, -1, -1,
+		);
+		let compact = x86_simd::_mm256_setr_epi32(0, 1, 2, 4, 5, 6, 7, 7);
+		let mut buffer = crate::Scratch([0u8; 32]);
+		for i in 0..n {
+			let x = map(x86_simd::_mm256_loadu_si256(input[32 * i..].as_ptr().cast()), &tables);
+			if x86_simd::_mm256_movemask_epi8(x) != 0 {
+				return 32 * i;
+			}
+			let x = x86_simd::_mm256_maddubs_epi16(x, x86_simd::_mm256_set1_epi32(0x0140_0140));
+			let x = x86_simd::_mm256_madd_epi16(x, x86_simd::_mm256_set1_epi32(0x0001_1000));
+			let x = x86_simd::_mm256_shuffle_epi8(x, gather);
+			let x = x86_simd::_mm256_permutevar8x32_epi32(x, compact);
+			x86_simd::_mm256_storeu_si256(buffer.as_mut_ptr().cast(), x);
+			output[24 * i..24 * i + 24].copy_from_slice(&buffer[..24]);
+		}
+	}
+	32 * n
+}
@@ -0,0 +1,318 @@
+//! Bech32 and Bech32m encodings
+//!
+//! This implements [BIP-173] and [BIP-350]. A string is a human-readable part, the separator
+//! `1`, the data part, and a checksum of 6 symbols. The data part encodes bytes with 5 bits per
+//! symbol, most significant bit first, and at most 4 zero bits of padding.
+//!
+//! Segwit addresses encode a 5-bit witness version before the program, so they are not bytes.
+//! They are decoded with [`decode_segwit`], while [`decode_u5`] returns the raw 5-bit values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::bech32::{self, Variant};
+//! let encoded = bech32::encode("hello", &[0x00, 0xff], Variant::Bech32m).unwrap();
+//! assert_eq!(encoded, "hello1qrlss3fhkp");
+//! let (hrp, data, variant) = bech32::decode("HELLO1QRLSS3FHKP").unwrap();
+//! assert_eq!(hrp, "hello");
+//! assert_eq!(data, [0x00, 0xff]);
+//! assert_eq!(variant, Variant::Bech32m);
+//! ```
+//!
+//! [BIP-173]: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! [BIP-350]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{DecodeError, DecodeKind, EncodeError, EncodeKind, Encoding};
+
+/// Maximum length of an encoded string
+pub const MAX_LENGTH: usize = 90;
+
+/// Maximum length of a human-readable part
+const MAX_HRP_LENGTH: usize = 83;
+
+/// Number of symbols of the checksum
+const CHECKSUM_LENGTH: usize = 6;
+
+/// Encoding of the data part, without checksum
+const DATA: Encoding = Encoding::internal_new(crate::data::BECH32_IMPL);
+
+/// Generator of the BCH code
+const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+/// Checksum variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Variant {
+	/// Original checksum of BIP-173.
+	Bech32,
+	/// Modified checksum of BIP-350.
+	Bech32m,
+}
+
+impl Variant {
+	/// Returns the value of the checksum polynomial for a valid string
+	const fn constant(self) -> u32 {
+		match self {
+			Variant::Bech32 => 1,
+			Variant::Bech32m => 0x2bc8_30a3,
+		}
+	}
+}
+
+/// Checksum polynomial being computed
+struct Polymod(u32);
+
+impl Polymod {
+	fn new(hrp: &[u8]) -> Polymod {
+		let mut polymod = Polymod(1);
+		for &x in hrp {
+			polymod.push(x >> 5);
+		}
+		polymod.push(0);
+		for &x in hrp {
+			polymod.push(x & 31);
+		}
+		polymod
+	}
+
+	fn push(&mut self, value: u8) {
+		let top = self.0 >> 25;
+		self.0 = (self.0 & 0x01ff_ffff) << 5 ^ u32::from(value);
+		for (i, generator) in GENERATOR.iter().enumerate() {
+			if top >> i & 1 == 1 {
+				self.0 ^= generator;
+			}
+		}
+	}
+}
+
+/// Returns whether `hrp` only has valid characters of a single case
+fn valid_hrp(hrp: &[u8]) -> bool {
+	let valid = hrp.iter().all(|&x| (33..=126).contains(&x));
+	let mixed = hrp.iter().any(u8::is_ascii_lowercase) && hrp.iter().any(u8::is_ascii_uppercase);
+	valid && !mixed
+}
+
+/// Returns `data` encoded with the human-readable part `hrp`
+///
+/// The output is lowercase, even if `hrp` is uppercase.
+///
+/// # Errors
+///
+/// Returns an error with kind [`Hrp`] if `hrp` is empty, longer than 83 characters, has
+/// characters outside `!` to `~`, or mixes cases. Returns an error with kind [`Overflow`] if the
+/// output would be longer than [`MAX_LENGTH`].
+///
+/// [`Hrp`]: crate::EncodeKind::Hrp
+/// [`Overflow`]: crate::EncodeKind::Overflow
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, EncodeError> {
+	let hrp = hrp.as_bytes();
+	check!(
+		EncodeError {
+			kind: EncodeKind::Hrp
+		},
+		(1..=MAX_HRP_LENGTH).contains(&hrp.len()) && valid_hrp(hrp)
+	);
+	let len = hrp.len() + 1 + DATA.encode_len(data.len())? + CHECKSUM_LENGTH;
+	check!(
+		EncodeError {
+			kind: EncodeKind::Overflow
+		},
+		len <= MAX_LENGTH
+	);
+	let mut output = String::with_capacity(len);
+	output.extend(hrp.iter().map(|&x| char::from(x.to_ascii_lowercase())));
+	output.push('1');
+	let start = output.len();
+	DATA.encode_append(data, &mut output);
+	let mut polymod = Polymod::new(&output.as_bytes()[..hrp.len()]);
+	let val = DATA.val();
+	output.as_bytes()[start..].iter().for_each(|&x| polymod.push(val[x as usize]));
+	(0..CHECKSUM_LENGTH).for_each(|_| polymod.push(0));
+	let checksum = polymod.0 ^ variant.constant();
+	let sym = DATA.sym();
+	for i in (0..CHECKSUM_LENGTH).rev() {
+		output.push(char::from(sym[(checksum >> (5 * i) & 31) as usize]));
+	}
+	Ok(output)
+}
+
+/// Returns the human-readable part, the data, and the checksum variant of `input`
+///
+/// The human-readable part is returned in lowercase. The input may be uppercase but not mixed
+/// case.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid:
+/// - [`Length`] if it is longer than [`MAX_LENGTH`], has no separator, has an empty
+///   human-readable part, or has a data part too short for the checksum or with more than 4
+///   bits of padding,
+/// - [`Symbol`] if a character is invalid or has a different case than the first letter,
+/// - [`Checksum`] if the checksum does not match any variant,
+/// - [`Trailing`] if the padding bits are not zero.
+///
+/// [`Length`]: crate::DecodeKind::Length
+/// [`Symbol`]: crate::DecodeKind::Symbol
+/// [`Checksum`]: crate::DecodeKind::Checksum
+/// [`Trailing`]: crate::DecodeKind::Trailing
+pub fn decode(input: &str) -> Result<(String, Vec<u8>, Variant), DecodeError> {
+	let (hrp, separator, variant) = check(input.as_bytes())?;
+	let data = decode_data(input.as_bytes(), separator + 1)?;
+	Ok((hrp, data, variant))
+}
+
+/// Returns the human-readable part, the 5-bit values, and the checksum variant of `input`
+///
+/// This is like [`decode`] but the data part is not converted to bytes. Each value is smaller
+/// than 32 and the checksum is not included.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode`], except for padding which is not checked.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::bech32::{self, Variant};
+/// let input = "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw";
+/// let (hrp, data, variant) = bech32::decode_u5(input).unwrap();
+/// assert_eq!(hrp, "abcdef");
+/// assert_eq!(data, (0 .. 32).collect::<Vec<u8>>());
+/// assert_eq!(variant, Variant::Bech32);
+/// ```
+pub fn decode_u5(input: &str) -> Result<(String, Vec<u8>, Variant), DecodeError> {
+	let (hrp, separator, variant) = check(input.as_bytes())?;
+	let val = DATA.val();
+	let end = input.len() - CHECKSUM_LENGTH;
+	let data = input.as_bytes()[separator + 1..end].iter().map(|&x| val[x as usize]).collect();
+	Ok((hrp, data, variant))
+}
+
+/// Returns the human-readable part, the witness version, and the witness program of a segwit
+/// address
+///
+/// The checksum must be [`Bech32`] for version 0 and [`Bech32m`] for other versions, as
+/// specified by [BIP-350]. The human-readable part is not checked against known networks.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode`], and additionally:
+/// - [`Length`] if there is no witness version, if the program is not between 2 and 40 bytes,
+///   or if a version 0 program is neither 20 nor 32 bytes,
+/// - [`Symbol`] if the witness version is greater than 16,
+/// - [`Checksum`] if the checksum variant does not match the witness version.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::bech32;
+/// let (hrp, version, program) = bech32::decode_segwit("BC1SW50QGDZ25J").unwrap();
+/// assert_eq!(hrp, "bc");
+/// assert_eq!(version, 16);
+/// assert_eq!(program, [0x75, 0x1e]);
+/// ```
+///
+/// [`Bech32`]: Variant::Bech32
+/// [`Bech32m`]: Variant::Bech32m
+/// [BIP-350]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+/// [`Length`]: crate::DecodeKind::Length
+/// [`Symbol`]: crate::DecodeKind::Symbol
+/// [`Checksum`]: crate::DecodeKind::Checksum
+pub fn decode_segwit(input: &str) -> Result<(String, u8, Vec<u8>), DecodeError> {
+	let (hrp, separator, variant) = check(input.as_bytes())?;
+	let start = separator + 1;
+	let length = DecodeError {
+		position: input.len(),
+		kind: DecodeKind::Length,
+	};
+	check!(length, input.len() - start > CHECKSUM_LENGTH);
+	let version = DATA.val()[input.as_bytes()[start] as usize];
+	check!(
+		DecodeError {
+			position: start,
+			kind: DecodeKind::Symbol
+		},
+		version <= 16
+	);
+	let expected = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+	check!(
+		DecodeError {
+			position: input.len(),
+			kind: DecodeKind::Checksum
+		},
+		variant == expected
+	);
+	let program = decode_data(input.as_bytes(), start + 1)?;
+	check!(length, (2..=40).contains(&program.len()));
+	check!(length, version != 0 || matches!(program.len(), 20 | 32));
+	Ok((hrp, version, program))
+}
+
+/// Checks `input` and returns its human-readable part, separator position, and variant
+fn check(input: &[u8]) -> Result<(String, usize, Variant), DecodeError> {
+	check!(
+		DecodeError {
+			position: MAX_LENGTH,
+			kind: DecodeKind::Length
+		},
+		input.len() <= MAX_LENGTH
+	);
+	let mut upper = None;
+	for (position, &x) in input.iter().enumerate() {
+		let error = DecodeError {
+			position,
+			kind: DecodeKind::Symbol,
+		};
+		check!(error, (33..=126).contains(&x));
+		if x.is_ascii_alphabetic() {
+			check!(error, *upper.get_or_insert(x.is_ascii_uppercase()) == x.is_ascii_uppercase());
+		}
+	}
+	let length = DecodeError {
+		position: input.len(),
+		kind: DecodeKind::Length,
+	};
+	let separator = input.iter().rposition(|&x| x == b'1').ok_or(length)?;
+	check!(
+		DecodeError {
+			position: 0,
+			kind: DecodeKind::Length
+		},
+		separator > 0
+	);
+	check!(length, input.len() - separator > CHECKSUM_LENGTH);
+	let hrp: String =
+		input[..separator].iter().map(|&x| char::from(x.to_ascii_lowercase())).collect();
+	let mut polymod = Polymod::new(hrp.as_bytes());
+	let val = DATA.val();
+	for (position, &x) in input.iter().enumerate().skip(separator + 1) {
+		let value = val[x as usize];
+		check!(
+			DecodeError {
+				position,
+				kind: DecodeKind::Symbol
+			},
+			value < 32
+		);
+		polymod.push(value);
+	}
+	let variant = [Variant::Bech32, Variant::Bech32m]
+		.into_iter()
+		.find(|variant| polymod.0 == variant.constant())
+		.ok_or(DecodeError {
+			position: input.len(),
+			kind: DecodeKind::Checksum,
+		})?;
+	Ok((hrp, separator, variant))
+}
+
+/// Decodes the data part of `input` from `start` to the checksum as bytes
+fn decode_data(input: &[u8], start: usize) -> Result<Vec<u8>, DecodeError> {
+	DATA.decode(&input[start..input.len() - CHECKSUM_LENGTH]).map_err(|mut error| {
+		error.position += start;
+		error
+	})
+}
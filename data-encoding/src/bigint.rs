@@ -113,6 +113,16 @@ impl<'a> BigUintView<'a> {
 		true
 	}
 
+	/// Number of bytes of the value, without leading zeros.
+	pub(crate) fn byte_len(&self) -> usize {
+		match self.chunks.get(self.start) {
+			None => 0,
+			Some(first) => {
+				(self.chunks.len() - self.start) * 4 - (first.leading_zeros() / 8) as usize
+			}
+		}
+	}
+
 	/// Copy bytes into an output buffer in big-endian format.
 	pub(crate) fn copy_into_bytes_be(&self, out: &mut [u8]) -> Result<usize, ()> {
 		if self.is_zero() {
@@ -151,6 +161,33 @@ impl<'a> BigUintView<'a> {
 	}
 }
 
+/// Buffer of chunks for a [`BigUintView`]
+///
+/// It holds the value being converted, which may be secret, so it is cleared on drop with the
+/// `zeroize` feature. It may be on the stack or on the heap.
+pub(crate) struct Chunks<T: AsMut<[u32]>>(pub(crate) T);
+
+impl<T: AsRef<[u32]> + AsMut<[u32]>> core::ops::Deref for Chunks<T> {
+	type Target = [u32];
+
+	fn deref(&self) -> &[u32] {
+		self.0.as_ref()
+	}
+}
+
+impl<T: AsRef<[u32]> + AsMut<[u32]>> core::ops::DerefMut for Chunks<T> {
+	fn deref_mut(&mut self) -> &mut [u32] {
+		self.0.as_mut()
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: AsMut<[u32]>> Drop for Chunks<T> {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(self.0.as_mut());
+	}
+}
+
 // Compatibility for alloc mode (when needed)
 #[cfg(feature = "alloc")]
 mod alloc_compat {
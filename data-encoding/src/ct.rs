@@ -0,0 +1,129 @@
+//! Constant-time encoding and decoding
+//!
+//! These functions do not branch on the data and do not index tables with it. Each symbol is
+//! looked up by scanning the whole table with masks, which is slower but does not leak the
+//! symbols through the cache. Only the lengths and the final success or failure are public.
+
+use crate::{DecodeError, DecodeKind};
+
+/// Returns `0xff` if `a == b` and `0` otherwise
+fn eq_mask(a: u8, b: u8) -> u8 {
+	let diff = u32::from(a ^ b);
+	#[allow(clippy::cast_possible_truncation)] // truncation on purpose
+	let mask = (diff.wrapping_sub(1) >> 8) as u8;
+	mask
+}
+
+/// Returns `table[x]`, or `default` if `x` is out of bounds
+fn lookup(table: &[u8], x: u8, default: u8) -> u8 {
+	let mut result = default;
+	for (i, &value) in table.iter().enumerate() {
+		#[allow(clippy::cast_possible_truncation)] // tables have at most 256 entries
+		let mask = eq_mask(i as u8, x);
+		result = (value & mask) | (result & !mask);
+	}
+	result
+}
+
+/// Encodes `input` in `output` without padding
+///
+/// The output must be exactly as long as the number of symbols.
+pub(crate) fn encode(bit: usize, msb: bool, sym: &[u8; 256], input: &[u8], output: &mut [u8]) {
+	debug_assert_eq!(output.len(), (8 * input.len()).div_ceil(bit));
+	let sym = &sym[..1 << bit];
+	let mask = (1u32 << bit) - 1;
+	let mut output = output.iter_mut();
+	let mut acc = 0u32;
+	let mut len = 0;
+	for &x in input {
+		if msb {
+			acc = acc << 8 | u32::from(x);
+		} else {
+			acc |= u32::from(x) << len;
+		}
+		len += 8;
+		while len >= bit {
+			len -= bit;
+			let value = if msb { acc >> len } else { acc };
+			if !msb {
+				acc >>= bit;
+			}
+			#[allow(clippy::cast_possible_truncation)] // masked
+			let value = (value & mask) as u8;
+			*output.next().unwrap() = lookup(sym, value, 0);
+		}
+		if msb {
+			acc &= (1 << len) - 1;
+		}
+	}
+	if len > 0 {
+		let value = if msb { acc << (bit - len) } else { acc };
+		#[allow(clippy::cast_possible_truncation)] // masked
+		let value = (value & mask) as u8;
+		*output.next().unwrap() = lookup(sym, value, 0);
+	}
+}
+
+/// Decodes `input` in `output` and returns the number of bytes written
+///
+/// The input must not contain padding. The output must be exactly as long as the number of
+/// whole bytes in the input.
+pub(crate) fn decode(
+	bit: usize,
+	msb: bool,
+	ctb: bool,
+	val: &[u8; 128],
+	input: &[u8],
+	output: &mut [u8],
+) -> Result<usize, DecodeError> {
+	debug_assert_eq!(output.len(), input.len() * bit / 8);
+	let mask = (1u32 << bit) - 1;
+	let mut written = 0;
+	let mut acc = 0u32;
+	let mut len = 0;
+	// All ones once an invalid symbol is found, and the position of the first one.
+	let mut invalid = 0usize;
+	let mut position = 0usize;
+	for (i, &x) in input.iter().enumerate() {
+		let value = u32::from(lookup(val, x, crate::INVALID));
+		// The value is too large for the bit width if subtracting it from the mask borrows.
+		let bad = (mask.wrapping_sub(value) >> 31) as usize;
+		let first = bad.wrapping_neg() & !invalid;
+		position = (i & first) | (position & !first);
+		invalid |= first;
+		let value = value & mask;
+		if msb {
+			acc = acc << bit | value;
+		} else {
+			acc |= value << len;
+		}
+		len += bit;
+		if len >= 8 {
+			len -= 8;
+			#[allow(clippy::cast_possible_truncation)] // truncation on purpose
+			let byte = if msb { acc >> len } else { acc } as u8;
+			output[written] = byte;
+			written += 1;
+			if msb {
+				acc &= (1 << len) - 1;
+			} else {
+				acc >>= 8;
+			}
+		}
+	}
+	check!(
+		DecodeError {
+			position,
+			kind: DecodeKind::Symbol
+		},
+		invalid == 0
+	);
+	check!(
+		DecodeError {
+			position: input.len().saturating_sub(1),
+			kind: DecodeKind::Trailing
+		},
+		!ctb || acc == 0
+	);
+	Ok(written)
+}
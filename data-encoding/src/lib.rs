@@ -51,6 +51,8 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
 #[cfg(feature = "alloc")]
 use alloc::borrow::ToOwned;
 #[cfg(feature = "alloc")]
@@ -69,8 +71,14 @@ use core::debug_assert as safety_assert;
 
 // Arithmetic encoding modules for non-power-of-two bases
 mod arithmetic;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod avx2;
 mod bigint;
 mod data;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(feature = "alloc")]
+mod sha256;
 
 macro_rules! check {
 	($e: expr, $c: expr) => {
@@ -80,10 +88,31 @@ macro_rules! check {
 	};
 }
 
+// The `?` operator is not available in const functions.
+macro_rules! const_try {
+	($e: expr) => {
+		match $e {
+			Ok(x) => x,
+			Err(e) => return Err(e),
+		}
+	};
+}
+
+// Declared after the macros it uses.
+#[cfg(feature = "alloc")]
+pub mod bech32;
+mod ct;
+#[cfg(feature = "alloc")]
+pub mod pem;
+pub mod radix;
+#[cfg(feature = "serde")]
+pub mod serde;
+
 /// Padding mode
 ///
 /// This mode is used when decoding to handle the padding characters.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum PaddingMode {
 	/// No padding is used.
 	None,
@@ -99,6 +128,10 @@ trait Static<T: Copy>: Copy {
 	fn val(self) -> T;
 }
 
+/// Symbol width with its block size
+///
+/// A block is at most 8 symbols and 8 bytes, so that it fits the fixed temporary buffers used
+/// for partial blocks (see [`Scratch`]). This is checked at compile time by `define_bit!`.
 trait BitWidth: Static<usize> {
 	const BIT: usize;
 	const ENC: usize;
@@ -131,6 +164,7 @@ macro_rules! define_bit {
 			const ENC: usize = $enc;
 			const DEC: usize = $dec;
 		}
+		const _: () = assert!($enc <= 8 && $dec <= 8, "block does not fit temporary buffers");
 	};
 }
 
@@ -297,7 +331,8 @@ fn vectorize<T, const N: usize>(slice: &[T]) -> (&[T], &[[T; N]], &[T]) {
 }
 
 /// Kind of decoding error
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum DecodeKind {
 	/// Invalid input length.
 	Length,
@@ -311,6 +346,12 @@ pub enum DecodeKind {
 	BufferTooSmall,
 	/// Big integer overflow.
 	Overflow,
+	/// Arithmetic base smaller than 2.
+	Base,
+	/// Invalid checksum.
+	Checksum,
+	/// Missing or invalid PEM boundary line.
+	Boundary,
 }
 
 impl core::fmt::Display for DecodeKind {
@@ -322,6 +363,9 @@ impl core::fmt::Display for DecodeKind {
 			DecodeKind::Padding => write!(f, "invalid padding"),
 			DecodeKind::BufferTooSmall => write!(f, "buffer too small"),
 			DecodeKind::Overflow => write!(f, "overflow"),
+			DecodeKind::Base => write!(f, "invalid base"),
+			DecodeKind::Checksum => write!(f, "invalid checksum"),
+			DecodeKind::Boundary => write!(f, "invalid boundary"),
 		}
 	}
 }
@@ -335,7 +379,7 @@ pub struct DecodeError {
 	pub kind: DecodeKind,
 }
 
-impl std::error::Error for DecodeError {}
+impl core::error::Error for DecodeError {}
 
 impl core::fmt::Display for DecodeError {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -344,12 +388,21 @@ impl core::fmt::Display for DecodeError {
 }
 
 /// Kind of encoding error
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum EncodeKind {
 	/// Output buffer too small.
 	BufferTooSmall,
-	/// Input too large for arithmetic encoding.
+	/// Input too large for arithmetic encoding or Bech32.
 	Overflow,
+	/// Arithmetic base smaller than 2.
+	Base,
+	/// Output length different from the encoded length.
+	Length,
+	/// Invalid Bech32 human-readable part.
+	Hrp,
+	/// Invalid PEM label or header.
+	Label,
 }
 
 impl core::fmt::Display for EncodeKind {
@@ -357,6 +410,10 @@ impl core::fmt::Display for EncodeKind {
 		match self {
 			EncodeKind::BufferTooSmall => write!(f, "buffer too small"),
 			EncodeKind::Overflow => write!(f, "overflow"),
+			EncodeKind::Base => write!(f, "invalid base"),
+			EncodeKind::Length => write!(f, "output length mismatch"),
+			EncodeKind::Hrp => write!(f, "invalid human-readable part"),
+			EncodeKind::Label => write!(f, "invalid label"),
 		}
 	}
 }
@@ -368,7 +425,7 @@ pub struct EncodeError {
 	pub kind: EncodeKind,
 }
 
-impl std::error::Error for EncodeError {}
+impl core::error::Error for EncodeError {}
 
 impl core::fmt::Display for EncodeError {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -387,48 +444,79 @@ pub struct DecodePartial {
 	pub error: DecodeError,
 }
 
-const INVALID: u8 = 128;
+/// Successful decoding result
+///
+/// See [`Encoding::decode_mut_full`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOutcome {
+	/// Number of bytes written to the output.
+	pub written: usize,
+	/// Number of ignored characters in the input.
+	pub ignored: usize,
+}
 
-fn encode_base64_simd(input: &[u8], output: &mut [u8], sym: &[u8; 256]) {
-	#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3"))]
-	{
-		let (_, mid, _) = vectorize::<u8, 12>(input);
-		let mut output = output;
-		for chunk in mid {
-			let input = unsafe { x86_simd::_mm_loadu_si128(chunk.as_ptr() as *const _) };
-			let mask = unsafe {
-				x86_simd::_mm_setr_epi8(2, 1, 0, 5, 4, 3, 8, 7, 6, 11, 10, 9, 128, 128, 128, 128)
-			};
-			let input = unsafe { x86_simd::_mm_shuffle_epi8(input, mask) };
-			let mask = unsafe { x86_simd::_mm_set1_epi32(0x0fc0_fc0f) };
-			let t0 = unsafe { x86_simd::_mm_and_si128(input, mask) };
-			let t1 = unsafe { x86_simd::_mm_and_si128(x86_simd::_mm_srli_epi32(input, 2), mask) };
-			let mask = unsafe {
-				x86_simd::_mm_setr_epi8(0, 2, 4, 6, 8, 10, 12, 14, 1, 3, 5, 7, 9, 11, 13, 15)
-			};
-			let res = unsafe { x86_simd::_mm_unpacklo_epi8(t0, t1) };
-			let res = unsafe { x86_simd::_mm_shuffle_epi8(res, mask) };
-			let low_mask = unsafe { x86_simd::_mm_set1_epi8(0x3f) };
-			let mut buffer = [0u8; 16];
-			unsafe { x86_simd::_mm_storeu_si128(buffer.as_mut_ptr() as *mut _, res) };
-			for (i, x) in buffer.iter().enumerate() {
-				output[i] = sym[(x & 0x3f) as usize];
-			}
-			output = &mut output[16..];
+/// Decoding error with the surrounding input
+///
+/// See [`Encoding::decode_verbose`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeErrorContext<'a> {
+	/// Decoding error.
+	pub error: DecodeError,
+	/// Input around the error position, up to 8 bytes on each side.
+	pub window: &'a [u8],
+	/// Position of the window in the input.
+	pub start: usize,
+	/// Input byte at the error position, if any.
+	pub byte: Option<u8>,
+}
+
+impl<'a> DecodeErrorContext<'a> {
+	#[cfg(feature = "alloc")]
+	fn new(input: &'a [u8], error: DecodeError) -> Self {
+		let position = core::cmp::min(error.position, input.len());
+		let start = position.saturating_sub(8);
+		let end = core::cmp::min(position.saturating_add(9), input.len());
+		DecodeErrorContext {
+			error,
+			window: &input[start..end],
+			start,
+			byte: input.get(position).copied(),
 		}
 	}
-	#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3")))]
-	{
-		let _ = (input, output, sym);
+}
+
+impl core::error::Error for DecodeErrorContext<'_> {
+	fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+		Some(&self.error)
 	}
 }
 
-fn encode_hex_simd(input: &[u8], output: &mut [u8], sym: &[u8; 256]) {
+impl core::fmt::Display for DecodeErrorContext<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}", self.error)?;
+		if let Some(byte) = self.byte {
+			write!(f, " (byte {byte:#04x})")?;
+		}
+		write!(f, " near \"{}\"", self.window.escape_ascii())
+	}
+}
+
+const INVALID: u8 = 128;
+
+/// Minimum length of an implementation: symbols, values, and the bit width or flags byte
+const MIN_IMPLEMENTATION_LEN: usize = 513;
+
+/// Encodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 16 bytes.
+fn encode_hex_simd(input: &[u8], output: &mut [u8], sym: &[u8; 256]) -> usize {
 	#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3"))]
 	{
-		let (_, mid, _) = vectorize::<u8, 16>(input);
+		let chunks = input.chunks_exact(16);
+		let len = input.len() - chunks.remainder().len();
 		let mut output = output;
-		for chunk in mid {
+		let mut buffer = Scratch([0u8; 32]);
+		for chunk in chunks {
 			let input = unsafe { x86_simd::_mm_loadu_si128(chunk.as_ptr() as *const _) };
 			let low_mask = unsafe { x86_simd::_mm_set1_epi8(0x0f) };
 			let low = unsafe { x86_simd::_mm_and_si128(input, low_mask) };
@@ -436,7 +524,6 @@ fn encode_hex_simd(input: &[u8], output: &mut [u8], sym: &[u8; 256]) {
 				unsafe { x86_simd::_mm_and_si128(x86_simd::_mm_srli_epi32(input, 4), low_mask) };
 			let res_low = unsafe { x86_simd::_mm_unpacklo_epi8(high, low) };
 			let res_high = unsafe { x86_simd::_mm_unpackhi_epi8(high, low) };
-			let mut buffer = [0u8; 32];
 			unsafe {
 				x86_simd::_mm_storeu_si128(buffer.as_mut_ptr() as *mut _, res_low);
 				x86_simd::_mm_storeu_si128(buffer.as_mut_ptr().add(16) as *mut _, res_high);
@@ -446,98 +533,137 @@ fn encode_hex_simd(input: &[u8], output: &mut [u8], sym: &[u8; 256]) {
 			}
 			output = &mut output[32..];
 		}
+		len
 	}
 	#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3")))]
 	{
 		let _ = (input, output, sym);
+		0
 	}
 }
 
-fn decode_hex_simd(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> Option<usize> {
-	#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3"))]
-	{
-		let (_, mid, _) = vectorize::<u8, 32>(input);
-		let mut output = output;
-		for (i, chunk) in mid.iter().enumerate() {
-			let mut buffer = [0u8; 32];
-			for (j, x) in chunk.iter().enumerate() {
-				if *x >= 128 || val[*x as usize] == INVALID {
-					return Some(i * 16 + j / 2);
-				}
-				buffer[j] = val[*x as usize];
-			}
-			let low = unsafe { x86_simd::_mm_loadu_si128(buffer.as_ptr() as *const _) };
-			let high = unsafe { x86_simd::_mm_loadu_si128(buffer.as_ptr().add(16) as *const _) };
-			let mask = unsafe {
-				x86_simd::_mm_setr_epi8(1, 3, 5, 7, 9, 11, 13, 15, 0, 2, 4, 6, 8, 10, 12, 14)
-			};
-			let low = unsafe { x86_simd::_mm_shuffle_epi8(low, mask) };
-			let high = unsafe { x86_simd::_mm_shuffle_epi8(high, mask) };
-			let res_low = unsafe {
-				x86_simd::_mm_or_si128(
-					x86_simd::_mm_slli_epi32(x86_simd::_mm_unpacklo_epi64(low, low), 4),
-					x86_simd::_mm_unpackhi_epi64(low, low),
-				)
-			};
-			let res_high = unsafe {
-				x86_simd::_mm_or_si128(
-					x86_simd::_mm_slli_epi32(x86_simd::_mm_unpacklo_epi64(high, high), 4),
-					x86_simd::_mm_unpackhi_epi64(high, high),
-				)
-			};
-			unsafe {
-				x86_simd::_mm_storeu_si128(output.as_mut_ptr() as *mut _, res_low);
-				x86_simd::_mm_storeu_si128(output.as_mut_ptr().add(8) as *mut _, res_high);
-			}
-			output = &mut output[16..];
-		}
+/// Encodes a prefix of `input` made of whole vectors and returns its length
+///
+/// This is only implemented with AVX2 on x86 and NEON on aarch64, selected at runtime, for
+/// hexadecimal and base64 with most significant bit first.
+fn encode_vector<B: BitWidth, BO: BitOrderTrait>(
+	sym: &[u8; 256],
+	input: &[u8],
+	output: &mut [u8],
+) -> usize {
+	#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+	if BO::MSB && (B::BIT == 4 || B::BIT == 6) && input.len() >= 32 && avx2::available() {
+		// SAFETY: AVX2 is available.
+		return match B::BIT {
+			4 => unsafe { avx2::encode_hex(input, output, sym) },
+			_ => unsafe { avx2::encode_base64(input, output, sym) },
+		};
 	}
-	#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3")))]
-	{
-		let _ = (input, output, val);
+	#[cfg(target_arch = "aarch64")]
+	if BO::MSB && (B::BIT == 4 || B::BIT == 6) && input.len() >= 32 && neon::available() {
+		// SAFETY: NEON is available.
+		return match B::BIT {
+			4 => unsafe { neon::encode_hex(input, output, sym) },
+			_ => unsafe { neon::encode_base64(input, output, sym) },
+		};
 	}
-	None
+	let _ = (sym, input, output);
+	0
 }
 
-fn decode_base64_simd(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> Option<usize> {
-	#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3"))]
-	{
-		let (_, mid, _) = vectorize::<u8, 16>(input);
-		let mut output = output;
-		for (i, chunk) in mid.iter().enumerate() {
-			let mut buffer = [0u8; 16];
-			for (j, x) in chunk.iter().enumerate() {
-				if *x >= 128 || val[*x as usize] >= 64 {
-					return Some(i * 12 + j * 3 / 4);
-				}
-				buffer[j] = val[*x as usize];
+/// Decodes a prefix of `input` made of whole vectors and returns its length
+///
+/// Decoding stops before the first vector with a non-symbol (invalid, ignored, or padding), which
+/// is left to the scalar code. See [`encode_vector`] for when this is implemented.
+fn decode_vector<B: BitWidth, BO: BitOrderTrait>(
+	val: &[u8; 128],
+	input: &[u8],
+	output: &mut [u8],
+) -> usize {
+	#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+	if BO::MSB && (B::BIT == 4 || B::BIT == 6) && input.len() >= 32 && avx2::available() {
+		// SAFETY: AVX2 is available.
+		return match B::BIT {
+			4 => unsafe { avx2::decode_hex(input, output, val) },
+			_ => unsafe { avx2::decode_base64(input, output, val) },
+		};
+	}
+	#[cfg(target_arch = "aarch64")]
+	if BO::MSB && (B::BIT == 4 || B::BIT == 6) && input.len() >= 32 && neon::available() {
+		// SAFETY: NEON is available.
+		return match B::BIT {
+			4 => unsafe { neon::decode_hex(input, output, val) },
+			_ => unsafe { neon::decode_base64(input, output, val) },
+		};
+	}
+	let _ = (val, input, output);
+	0
+}
+
+/// Returns the length of a prefix of `input` made of whole vectors of symbols
+///
+/// This validates symbols for any bit width and bit order, with the same kernels and runtime
+/// selection as [`decode_vector`]. The prefix may stop before the first non-symbol, which is left
+/// to the scalar code for an exact error position.
+fn symbols_vector(val: &[u8; 128], input: &[u8]) -> usize {
+	#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+	if input.len() >= 32 && avx2::available() {
+		// SAFETY: AVX2 is available.
+		return unsafe { avx2::symbols(input, val) };
+	}
+	#[cfg(target_arch = "aarch64")]
+	if input.len() >= 16 && neon::available() {
+		// SAFETY: NEON is available.
+		return unsafe { neon::symbols(input, val) };
+	}
+	let _ = (val, input);
+	0
+}
+
+/// Decodes the whole blocks of `input` in `output` and returns the length of input decoded
+///
+/// Every byte of `input` must be a symbol, so decoding cannot fail.
+fn decode_symbols<B: BitWidth, BO: BitOrderTrait>(
+	val: &[u8; 128],
+	input: &[u8],
+	output: &mut [u8],
+) -> usize {
+	let blocks = input.chunks_exact(B::ENC).zip(output.chunks_exact_mut(B::DEC));
+	let mut read = 0;
+	// A block holds at most 40 bits.
+	let mut acc = Scratch(0u64);
+	for (input, output) in blocks {
+		*acc = 0;
+		for (i, &x) in input.iter().enumerate() {
+			let x = u64::from(val[usize::from(x)]);
+			if BO::MSB {
+				*acc = *acc << B::BIT | x;
+			} else {
+				*acc |= x << (B::BIT * i);
 			}
-			let input = unsafe { x86_simd::_mm_loadu_si128(buffer.as_ptr() as *const _) };
-			let mask = unsafe {
-				x86_simd::_mm_setr_epi8(0, 2, 4, 6, 8, 10, 12, 14, 1, 3, 5, 7, 9, 11, 13, 15)
-			};
-			let input = unsafe { x86_simd::_mm_shuffle_epi8(input, mask) };
-			let t0 = unsafe { x86_simd::_mm_unpacklo_epi8(input, x86_simd::_mm_setzero_si128()) };
-			let t1 = unsafe { x86_simd::_mm_unpackhi_epi8(input, x86_simd::_mm_setzero_si128()) };
-			let res = unsafe { x86_simd::_mm_or_si128(t0, x86_simd::_mm_slli_epi32(t1, 2)) };
-			let mask = unsafe {
-				x86_simd::_mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 128, 128, 128, 128)
-			};
-			let res = unsafe { x86_simd::_mm_shuffle_epi8(res, mask) };
-			unsafe { x86_simd::_mm_storeu_si128(output.as_mut_ptr() as *mut _, res) };
-			output = &mut output[12..];
 		}
+		for (j, y) in output.iter_mut().enumerate() {
+			let shift = if BO::MSB { 8 * (B::DEC - 1 - j) } else { 8 * j };
+			#[allow(clippy::cast_possible_truncation)] // only keeps the byte at `shift`
+			let byte = (*acc >> shift) as u8;
+			*y = byte;
+		}
+		read += B::ENC;
 	}
-	#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3")))]
-	{
-		let _ = (input, output, val);
-	}
-	None
+	read
 }
 
 const IGNORE: u8 = 129;
 const PADDING: u8 = 130;
 
+/// Number of input bytes or symbols processed per task (see [`Encoding::par_encode`])
+#[cfg(feature = "rayon")]
+const PAR_CHUNK: usize = 1 << 16;
+
+/// Check symbols for the values 32 to 36 (see [`Encoding::encode_check_symbol`])
+#[cfg(feature = "alloc")]
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
 const fn order(bit: usize, msb: bool, i: usize) -> usize {
 	if msb {
 		bit - 1 - i
@@ -549,7 +675,8 @@ const fn order(bit: usize, msb: bool, i: usize) -> usize {
 const fn enc(bit: usize) -> usize {
 	match bit {
 		1 | 2 | 4 => 8 / bit,
-		3 | 5 | 6 => 8,
+		3 | 5 => 8,
+		6 => 4,
 		_ => 0,
 	}
 }
@@ -592,19 +719,17 @@ fn encode_mut<B: BitWidth, BO: BitOrderTrait>(
 ) -> usize {
 	let mut input = input;
 	let mut output = output;
-	if B::BIT == 6 && BO::MSB {
-		encode_base64_simd(input, output, sym);
-		let n = floor(input.len(), 12);
-		input = &input[n..];
-		output = &mut output[n / 3 * 4..];
-	}
+	let mut written = 0;
+	let n = encode_vector::<B, BO>(sym, input, output);
+	input = &input[n..];
+	output = &mut output[n / B::DEC * B::ENC..];
+	written += n / B::DEC * B::ENC;
 	if B::BIT == 4 && BO::MSB {
-		encode_hex_simd(input, output, sym);
-		let n = floor(input.len(), 16);
+		let n = encode_hex_simd(input, output, sym);
 		input = &input[n..];
 		output = &mut output[n * 2..];
+		written += n * 2;
 	}
-	let mut written = 0;
 	while input.len() >= B::DEC {
 		encode_block::<B, BO>(sym, &input[0..B::DEC], &mut output[0..B::ENC]);
 		input = &input[B::DEC..];
@@ -654,28 +779,6 @@ fn decode_mut<B: BitWidth, BO: BitOrderTrait>(
 ) -> Result<usize, DecodeError> {
 	let mut input = input;
 	let mut output = output;
-	if B::BIT == 4 && BO::MSB {
-		if let Some(pos) = decode_hex_simd(input, output, val) {
-			return Err(DecodeError {
-				position: pos * 2,
-				kind: DecodeKind::Symbol,
-			});
-		}
-		let n = floor(input.len(), 32);
-		input = &input[n..];
-		output = &mut output[n / 2..];
-	}
-	if B::BIT == 6 && BO::MSB {
-		if let Some(pos) = decode_base64_simd(input, output, val) {
-			return Err(DecodeError {
-				position: pos / 3 * 4,
-				kind: DecodeKind::Symbol,
-			});
-		}
-		let n = floor(input.len(), 16);
-		input = &input[n..];
-		output = &mut output[n / 4 * 3..];
-	}
 	let mut written = 0;
 	let mut position = 0;
 	while input.len() >= B::ENC {
@@ -716,8 +819,10 @@ fn check_trail<B: BitWidth, BO: BitOrderTrait>(
 	if last >= 128 {
 		return Err(DecodeKind::Symbol);
 	}
+	// Only the bits past the last complete output byte are trailing.
+	let used = input.len() * B::BIT / 8 * 8;
 	let mut k = B::BIT;
-	while j > 8 * B::DEC {
+	while j > used {
 		j -= 1;
 		k -= 1;
 		if (last >> order(B::BIT, BO::MSB, k)) & 1 != 0 {
@@ -785,8 +890,10 @@ fn encode_pad<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 	let mut written =
 		encode_mut::<B, BO>(sym, &input[0..input_full_len], &mut output[0..output_full_len]);
 	let remaining_input = &input[input_full_len..];
+	// Blocks are a single byte when the bit width divides 8, so there is no partial block.
+	safety_assert!(8 % B::BIT != 0 || remaining_input.is_empty());
 	if !remaining_input.is_empty() {
-		let mut block = [0u8; 32];
+		let mut block = Scratch([0u8; 8]);
 		encode_block::<B, BO>(sym, remaining_input, &mut block[0..enc]);
 		let len = olen - written;
 		output[written..olen].copy_from_slice(&block[0..len]);
@@ -818,6 +925,49 @@ fn encode_wrap_len<B: BitWidth, PM: PaddingTrait>(
 	}
 }
 
+/// Decodes at most 16 symbols of canonical input
+///
+/// Returns `None` for anything else than symbols followed by the padding of the encoded length,
+/// in which case the caller falls back to the general path to report the exact error.
+fn decode_small(
+	encoding: &Encoding,
+	bit: usize,
+	msb: bool,
+	input: &[u8],
+	output: &mut [u8],
+) -> Option<usize> {
+	safety_assert!(input.len() <= 16);
+	let val = encoding.val();
+	let value = |x: u8| if x < 128 { val[x as usize] } else { INVALID };
+	let len = input.len() - input.iter().rev().take_while(|&&x| value(x) == PADDING).count();
+	let olen = bit * len / 8;
+	if div_ceil(8 * olen, bit)? != len || encoding.encode_len(olen).ok()? != input.len() {
+		return None;
+	}
+	let mut acc = Scratch(0u128);
+	for (i, &x) in input[..len].iter().enumerate() {
+		let v = value(x);
+		if v >= 128 {
+			return None;
+		}
+		*acc |= u128::from(v) << if msb { bit * (len - 1 - i) } else { bit * i };
+	}
+	let extra = bit * len - 8 * olen;
+	let trailing = if msb { *acc & ((1 << extra) - 1) } else { *acc >> (8 * olen) };
+	if trailing != 0 && encoding.ctb() {
+		return None;
+	}
+	if msb {
+		*acc >>= extra;
+	}
+	for (i, y) in output[..olen].iter_mut().enumerate() {
+		#[allow(clippy::cast_possible_truncation)] // keeps the low byte
+		let byte = (*acc >> if msb { 8 * (olen - 1 - i) } else { 8 * i }) as u8;
+		*y = byte;
+	}
+	Some(olen)
+}
+
 fn encode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 	_bit: B,
 	_msb: BO,
@@ -828,19 +978,29 @@ fn encode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 	input: &[u8],
 	output: &mut [u8],
 ) -> usize {
-	let olen = encode_pad_len::<B, PM>(input.len()).unwrap();
 	match wrap {
 		None => encode_pad::<B, BO, PM>(sym, pad, input, output),
 		Some((col, end)) => {
-			let mut temp = [0u8; 8];
+			// Holds one block of symbols, see BitWidth.
+			let mut temp = Scratch([0u8; 8]);
 			let mut written = 0;
 			let mut i = 0;
 			let mut j = 0;
 			while i < input.len() {
 				let n = core::cmp::min(B::DEC, input.len() - i);
 				encode_block::<B, BO>(sym, &input[i..i + n], &mut temp[0..B::ENC]);
-				for k in 0..B::ENC {
-					output[written] = temp[k];
+				// A partial block only carries `data` symbols, the rest is padding (if any).
+				let data = div_ceil(n * 8, B::BIT).unwrap();
+				let count = if PM::MODE == PaddingMode::None {
+					data
+				} else {
+					B::ENC
+				};
+				for (k, &x) in temp[0..count].iter().enumerate() {
+					output[written] = match pad {
+						Some(pad) if k >= data => pad,
+						_ => x,
+					};
 					written += 1;
 					j += 1;
 					if j == col {
@@ -851,18 +1011,6 @@ fn encode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 				}
 				i += n;
 			}
-			if let Some(pad) = pad {
-				while written < olen {
-					output[written] = pad;
-					written += 1;
-					j += 1;
-					if j == col {
-						output[written..written + end.len()].copy_from_slice(end);
-						written += end.len();
-						j = 0;
-					}
-				}
-			}
 			if j != 0 {
 				output[written..written + end.len()].copy_from_slice(end);
 				written += end.len();
@@ -883,8 +1031,13 @@ fn decode_wrap_len(bit: usize, pm: PaddingMode, len: usize) -> Option<(usize, us
 	};
 	match pm {
 		PaddingMode::None => {
+			// A partial block is valid only if it does not leave a whole symbol of trailing bits.
+			let mut ilen = len;
+			while ilen % enc * bit % 8 >= bit {
+				ilen -= 1;
+			}
 			let olen = (len * bit) / 8;
-			Some((len, olen))
+			Some((ilen, olen))
 		}
 		_ => {
 			let ilen = floor(len, enc);
@@ -942,8 +1095,8 @@ fn decode_base_mut<B: BitWidth, BO: BitOrderTrait>(
 	}
 	if !input.is_empty() {
 		let n = (input.len() * B::BIT) / 8;
-		let mut temp = [0u8; 8];
-		let mut out_temp = [0u8; 8];
+		let mut temp = Scratch([0u8; 8]);
+		let mut out_temp = Scratch([0u8; 8]);
 		temp[0..input.len()].copy_from_slice(input);
 		decode_block::<B, BO>(val, &temp[0..B::ENC], &mut out_temp[0..B::DEC]).map_err(|kind| {
 			DecodeError {
@@ -998,8 +1151,8 @@ fn decode_pad_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 			let mut written =
 				decode_mut::<B, BO>(ctb, val, &input[0..n * B::ENC], &mut output[0..olen])?;
 			if i < n * B::ENC {
-				let mut temp = [0u8; 8];
-				let mut out_temp = [0u8; 8];
+				let mut temp = Scratch([0u8; 8]);
+				let mut out_temp = Scratch([0u8; 8]);
 				temp[0..B::ENC].fill(input[i]);
 				for j in 0..i % B::ENC {
 					temp[j] = input[i - i % B::ENC + j];
@@ -1021,6 +1174,48 @@ fn decode_pad_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait>(
 	}
 }
 
+/// Temporary buffer for encoding and decoding
+///
+/// It usually holds one block of symbols or bytes, which is at most 8 (see [`BitWidth`]), but
+/// also larger buffers and integer accumulators. It may hold secret data, so it is cleared on
+/// drop with the `zeroize` feature.
+struct Scratch<T: Wipe>(T);
+
+/// Values that [`Scratch`] can clear
+#[cfg(feature = "zeroize")]
+trait Wipe: zeroize::Zeroize {}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> Wipe for T {}
+
+/// Values that [`Scratch`] can clear
+#[cfg(not(feature = "zeroize"))]
+trait Wipe {}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> Wipe for T {}
+
+impl<T: Wipe> core::ops::Deref for Scratch<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T: Wipe> core::ops::DerefMut for Scratch<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Wipe> Drop for Scratch<T> {
+	fn drop(&mut self) {
+		self.0.zeroize();
+	}
+}
+
 fn skip_ignore(val: &[u8; 128], input: &[u8]) -> usize {
 	let mut i = 0;
 	while i < input.len() && (input[i] >= 128 || val[input[i] as usize] == IGNORE) {
@@ -1034,7 +1229,7 @@ fn decode_wrap_block<B: BitWidth, I: IgnoreTrait>(
 	input: &mut &[u8],
 	output: &mut [u8],
 ) -> Result<(), DecodeKind> {
-	let mut buffer = [0u8; 8];
+	let mut buffer = Scratch([0u8; 8]);
 	for i in 0..B::ENC {
 		if I::IGNORE {
 			let n = skip_ignore(val, input);
@@ -1049,6 +1244,56 @@ fn decode_wrap_block<B: BitWidth, I: IgnoreTrait>(
 	decode_block::<B, Bt>(val, &buffer[0..B::ENC], output)
 }
 
+/// # Safety
+///
+/// Every byte of `input` must be a symbol, a padding character, or an ignored character.
+#[cfg(feature = "unsafe-unchecked")]
+unsafe fn decode_unchecked_mut<B: BitWidth, BO: BitOrderTrait, I: IgnoreTrait>(
+	_bit: B,
+	_msb: BO,
+	_has_ignore: I,
+	val: &[u8; 128],
+	input: &[u8],
+	output: &mut [u8],
+) -> usize {
+	let group = if B::BIT == 6 { 4 } else { B::ENC };
+	let mut written = 0;
+	let mut acc = 0u32;
+	let mut bits = 0;
+	let mut len = 0;
+	for &byte in input {
+		let x = unsafe { *val.get_unchecked(byte as usize) };
+		if x != PADDING {
+			if I::IGNORE && x == IGNORE {
+				continue;
+			}
+			if BO::MSB {
+				acc = acc << B::BIT | u32::from(x);
+			} else {
+				acc |= u32::from(x) << bits;
+			}
+			bits += B::BIT;
+			if bits >= 8 {
+				bits -= 8;
+				#[allow(clippy::cast_possible_truncation)] // keeps the complete byte
+				let byte = if BO::MSB { (acc >> bits) as u8 } else { acc as u8 };
+				output[written] = byte;
+				if !BO::MSB {
+					acc >>= 8;
+				}
+				written += 1;
+			}
+		}
+		len += 1;
+		if len == group {
+			acc = 0;
+			bits = 0;
+			len = 0;
+		}
+	}
+	written
+}
+
 fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTrait>(
 	_bit: B,
 	_msb: BO,
@@ -1057,7 +1302,6 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 	ctb: bool,
 	val: &[u8; 128],
 	sym: &[u8; 256],
-	pad_char: Option<u8>,
 	input: &[u8],
 	output: &mut [u8],
 ) -> Result<usize, DecodePartial> {
@@ -1065,19 +1309,42 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 	let mut output = output;
 	let mut read = 0;
 	let mut written = 0;
+	// Position from which to try decoding whole vectors again, which is after the vector with a
+	// non-symbol or after ignored characters.
+	let mut vector = 0;
 
 	while !input.is_empty() {
 		if I::IGNORE {
 			let n = skip_ignore(val, input);
 			input = &input[n..];
 			read += n;
+			if n > 0 {
+				vector = read;
+			}
+		}
+		if read >= vector {
+			let n = decode_vector::<B, BO>(val, input, output);
+			input = &input[n..];
+			read += n;
+			output = &mut output[n / B::ENC * B::DEC..];
+			written += n / B::ENC * B::DEC;
+			// Validates the symbols that the kernel did not decode, in particular for bit widths
+			// without kernels. The rest of the vector with the first non-symbol is left below.
+			let n = symbols_vector(val, input);
+			let n = decode_symbols::<B, BO>(val, &input[..n], output);
+			input = &input[n..];
+			read += n;
+			output = &mut output[n / B::ENC * B::DEC..];
+			written += n / B::ENC * B::DEC;
+			// Vectors have at most 32 bytes when they stop.
+			vector = read + 32;
 		}
 		if input.is_empty() {
 			break;
 		}
 
 		let start_input = input;
-		let mut buffer = [0u8; 8];
+		let mut buffer = Scratch([0u8; 8]);
 		let mut b_idx = 0;
 		let mut p_idx = None;
 
@@ -1092,7 +1359,8 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 				break;
 			}
 			let byte = input[0];
-			if Some(byte) == pad_char {
+			// The padding character and characters translated to it all decode as padding.
+			if byte < 128 && val[byte as usize] == PADDING {
 				if p_idx.is_none() {
 					p_idx = Some(b_idx);
 				}
@@ -1128,12 +1396,23 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 		}
 
 		if b_idx < B::ENC {
+			if PM::MODE == PaddingMode::None && p_idx.is_none() && (b_idx * B::BIT) % 8 >= B::BIT {
+				// Only reachable with ignored characters, otherwise `decode_len` rejects it.
+				return Err(DecodePartial {
+					read,
+					written,
+					error: DecodeError {
+						position: read + (start_input.len() - input.len()) - 1,
+						kind: DecodeKind::Length,
+					},
+				});
+			}
 			if PM::MODE == PaddingMode::None {
 				for i in b_idx..B::ENC {
 					buffer[i] = sym[0];
 				}
 				let n = (b_idx * B::BIT) / 8;
-				let mut out_temp = [0u8; 8];
+				let mut out_temp = Scratch([0u8; 8]);
 				if let Err(kind) =
 					decode_block::<B, BO>(val, &buffer[0..B::ENC], &mut out_temp[0..B::DEC])
 				{
@@ -1184,7 +1463,7 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 			}
 		}
 
-		let mut out_temp = [0u8; 8];
+		let mut out_temp = Scratch([0u8; 8]);
 		if let Err(kind) = decode_block::<B, BO>(val, &buffer[0..B::ENC], &mut out_temp[0..B::DEC])
 		{
 			return Err(DecodePartial {
@@ -1198,6 +1477,17 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 		}
 
 		let n = if let Some(p) = p_idx {
+			// A group made only of padding characters is never produced by encoding.
+			if p == 0 {
+				return Err(DecodePartial {
+					read: read + (start_input.len() - input.len()),
+					written,
+					error: DecodeError {
+						position: read + (start_input.len() - input.len()) - B::ENC,
+						kind: DecodeKind::Padding,
+					},
+				});
+			}
 			if (p * B::BIT) % 8 >= B::BIT {
 				return Err(DecodePartial {
 					read: read + (start_input.len() - input.len()),
@@ -1232,6 +1522,8 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 		read += start_input.len() - input.len();
 
 		if p_idx.is_some() && PM::MODE == PaddingMode::PadFinal {
+			// Only ignored characters may follow the final padding. `skip_ignore` consumes the
+			// whole run, so separators like "\r\n" are accepted.
 			if I::IGNORE {
 				let n = skip_ignore(val, input);
 				input = &input[n..];
@@ -1254,7 +1546,8 @@ fn decode_wrap_mut<B: BitWidth, BO: BitOrderTrait, PM: PaddingTrait, I: IgnoreTr
 }
 
 /// Bit order
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum BitOrder {
 	/// Most significant bit first
 	MostSignificantFirst,
@@ -1289,15 +1582,19 @@ pub struct Encoding(InternalEncoding);
 
 /// Character translation
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Translate {
 	/// Characters to translate from.
 	pub from: String,
 	/// Characters to translate to.
+	///
+	/// This may be the padding character, so that several characters decode as padding.
 	pub to: String,
 }
 
 /// Output wrapping
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Wrap {
 	/// Wrap width.
 	pub width: usize,
@@ -1307,11 +1604,17 @@ pub struct Wrap {
 
 /// Encoding specification
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::struct_excessive_bools)] // independent options
 pub struct Specification {
 	/// Symbols used by the encoding.
 	///
 	/// The symbols are ordered by their value. For example, in hexadecimal, "0" has value 0 and "F"
-	/// has value 15. The length of the symbols must be a power of 2.
+	/// has value 15. If the number of symbols is a power of 2 up to 64, each symbol encodes a
+	/// fixed number of bits. Any other number of symbols (e.g. 63 for an alphabet missing one
+	/// character) selects arithmetic encoding in that base, unless
+	/// [`forbid_arithmetic`](Self::forbid_arithmetic) is set.
 	pub symbols: String,
 
 	/// Bit order.
@@ -1325,6 +1628,9 @@ pub struct Specification {
 	pub check_trailing_bits: bool,
 
 	/// Padding character.
+	///
+	/// This is the character used when encoding. Other characters can decode as padding by
+	/// translating them to it.
 	pub padding: Option<char>,
 
 	/// Padding mode.
@@ -1339,8 +1645,77 @@ pub struct Specification {
 	/// Character translation.
 	pub translate: Translate,
 
+	/// Whether decoding is case-insensitive.
+	///
+	/// If true, the other case of each letter in [`symbols`](Self::symbols) decodes like the
+	/// letter, as if the pairs were added to [`translate`](Self::translate). Encoding still uses
+	/// the symbols as given. This is invalid if a letter is a symbol in both cases, like in base64.
+	/// The [`Encoding::specification`] of the resulting encoding lists the pairs in `translate`.
+	pub case_insensitive: bool,
+
 	/// Force use of arithmetic encoding.
 	pub use_arithmetic: bool,
+
+	/// Forbid the automatic fallback to arithmetic encoding.
+	///
+	/// By default, symbols whose count is not a power of 2 select arithmetic encoding. If true,
+	/// they are rejected instead, unless [`use_arithmetic`](Self::use_arithmetic) is set.
+	pub forbid_arithmetic: bool,
+
+	/// Whether to accept a trailing group made only of padding characters.
+	///
+	/// If true, the decoding functions ignore a final group of padding characters (as many as
+	/// there are symbols in a block) instead of returning an error. The rest of the input is
+	/// validated as usual. Encodings without a padding character use `=` for this purpose.
+	pub allow_trailing_pad_group: bool,
+
+	/// Number of bytes per block for arithmetic encoding.
+	///
+	/// By default (zero), arithmetic encoding converts the whole input as a single big integer,
+	/// which takes quadratic time. Otherwise, the input is split in blocks of this many bytes
+	/// (at most 16), each encoded independently as a fixed number of symbols, which takes linear
+	/// time. The last block may be shorter and is encoded as the number of symbols for its
+	/// length. For example, 8-byte blocks in base58 are encoded as 11 symbols like Monero does,
+	/// and 4-byte blocks in base85 as 5 symbols like Z85 does. Leading zero bytes are not treated
+	/// specially in this mode.
+	///
+	/// This is only valid for arithmetic encodings.
+	pub arithmetic_block_size: usize,
+
+	/// Whether the last block is padded with zeros, in block mode.
+	///
+	/// By default, a last block shorter than [`arithmetic_block_size`] is encoded as a shorter
+	/// number. If true, it is padded with zero bytes, encoded as a whole block, and only the
+	/// first symbols needed to decode it are kept, like Ascii85 does. The encoding of a prefix of
+	/// the input is then a prefix of the encoding of the input, up to the last block.
+	///
+	/// This is only valid in block mode.
+	///
+	/// [`arithmetic_block_size`]: Self::arithmetic_block_size
+	pub arithmetic_pad_last_block: bool,
+
+	/// Symbol abbreviating a whole block of zero bytes, in block mode.
+	///
+	/// For example, Ascii85 encodes 4 zero bytes as `z` instead of `!!!!!`. This symbol must not
+	/// be one of the [`symbols`], and the last block is never abbreviated if it is shorter.
+	///
+	/// This is only valid in block mode.
+	///
+	/// [`symbols`]: Self::symbols
+	pub arithmetic_zero_block: Option<char>,
+
+	/// Whether encoding and decoding run in constant time.
+	///
+	/// If true, [`Encoding::encode_mut`] and [`Encoding::decode_mut`], and the functions built on
+	/// them like [`Encoding::encode`] and [`Encoding::decode`], neither branch on the data nor
+	/// index tables with it, so the timing only depends on the lengths. This is meant for
+	/// secrets like keys and tokens, and is several times slower. Decoding processes the whole
+	/// input before reporting an invalid symbol, and reports padding characters before the final
+	/// padding as invalid symbols.
+	///
+	/// This is only valid for encodings which are not arithmetic, do not ignore characters, do
+	/// not wrap, and use [`PaddingMode::Standard`] if they pad.
+	pub constant_time: bool,
 }
 
 impl Default for Specification {
@@ -1356,11 +1731,16 @@ impl Encoding {
 
 	fn sym(&self) -> &[u8; 256] {
 		let data = self.data();
+		safety_assert!(data.len() >= MIN_IMPLEMENTATION_LEN);
+		// SAFETY: Implementations are at least MIN_IMPLEMENTATION_LEN bytes long (checked by
+		// internal_new and guaranteed by Specification::encoding).
 		unsafe { &*(data.as_ptr() as *const [u8; 256]) }
 	}
 
 	fn val(&self) -> &[u8; 128] {
 		let data = self.data();
+		safety_assert!(data.len() >= MIN_IMPLEMENTATION_LEN);
+		// SAFETY: Same as sym, bytes 256 to 384 are in bounds.
 		unsafe { &*(data.as_ptr().add(256) as *const [u8; 128]) }
 	}
 
@@ -1372,6 +1752,11 @@ impl Encoding {
 		data.len() >= 514 && (data[513] & 0x80) != 0
 	}
 
+	/// Get the lookup table of arithmetic decoding, with case folding and translation
+	fn arithmetic_lookup(&self) -> [u8; 256] {
+		arithmetic::build_lookup_values(self.val(), self.get_symbols().len())
+	}
+
 	/// Get the symbols for this encoding
 	fn get_symbols(&self) -> &[u8] {
 		let data = self.data();
@@ -1476,30 +1861,128 @@ impl Encoding {
 	}
 
 	/// Minimum number of input and output blocks when encoding
+	///
+	/// The input length is in bytes and the output length in symbols. When wrapping, the output
+	/// covers a whole number of lines.
 	fn block_len(&self) -> (usize, usize) {
 		let bit = self.bit();
+		let ilen = dec(bit);
+		let olen = ilen * 8 / bit;
 		match self.wrap() {
-			Some((col, end)) => (col / dec(bit) * enc(bit), col + end.len()),
-			None => (enc(bit), dec(bit)),
+			Some((col, end)) => {
+				let mut lines = 1;
+				while col * lines % olen != 0 {
+					lines += 1;
+				}
+				(col * lines / olen * ilen, lines * (col + end.len()))
+			}
+			None => (ilen, olen),
 		}
 	}
 
-	fn wrap(&self) -> Option<(usize, &[u8])> {
-		match &self.0 {
-			InternalEncoding::Static(data) => {
-				if data.len() <= 514 {
-					return None;
-				}
-				let col = data[514] as usize;
-				if col == 0 {
-					return None;
-				}
-				Some((col, &data[515..]))
-			}
-			InternalEncoding::Owned(data) => {
-				let col = data[514] as usize;
-				if col == 0 {
-					return None;
+	/// Whether a trailing group of padding characters is accepted when decoding
+	///
+	/// This flag is stored in the unused upper half of the value table. Static encodings have
+	/// `INVALID` there, which reads as false.
+	fn allow_trailing_pad_group(&self) -> bool {
+		let data = self.data();
+		data.len() > 511 && data[511] & 0x01 != 0
+	}
+
+	/// Whether encoding and decoding run in constant time
+	///
+	/// This flag is stored next to the one above.
+	fn constant_time(&self) -> bool {
+		let data = self.data();
+		data.len() > 511 && data[511] & 0x02 != 0
+	}
+
+	/// Block configuration for arithmetic encodings in block mode
+	///
+	/// This is stored in the unused upper half of the value table, like the flag above. The block
+	/// size is in the low 5 bits and padding the last block in bit 5, and the zero-block symbol
+	/// (if ASCII) is in the byte before. Static encodings have `INVALID` there, which reads as
+	/// none.
+	fn arithmetic_block(&self) -> Option<arithmetic::Blocks> {
+		let data = self.data();
+		let (&zero, &info) = (data.get(509)?, data.get(510)?);
+		let size = (info & 0x1f) as usize;
+		let valid = self.is_arithmetic() && (1..=arithmetic::MAX_BLOCK_SIZE).contains(&size);
+		valid.then_some(arithmetic::Blocks {
+			size,
+			pad_last: info & 0x20 != 0,
+			zero: (zero < 128).then_some(zero),
+		})
+	}
+
+	/// Input and output lengths of a block for arithmetic encodings in block mode
+	///
+	/// The input length is in bytes and the output length in symbols, like [`block_len`].
+	///
+	/// [`block_len`]: struct.Encoding.html#method.block_len
+	fn arithmetic_block_len(&self) -> Option<(usize, usize)> {
+		let size = self.arithmetic_block()?.size;
+		Some((size, arithmetic::block_symbols(self.get_symbols().len(), size)))
+	}
+
+	/// Number of symbols in a padded group
+	fn pad_group(&self) -> usize {
+		enc(self.bit())
+	}
+
+	/// Decodes `input` in `output` in constant time
+	///
+	/// Only the position of the padding is computed with branches, but it is deduced from the
+	/// output length anyway.
+	fn decode_constant_time(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+		let bit = self.bit();
+		let mut len = input.len();
+		if let (Some(pad), PaddingMode::Standard) = (self.pad(), self.pad_mode()) {
+			let group = self.pad_group();
+			while len > 0 && input.len() - len < group && input[len - 1] == pad {
+				len -= 1;
+			}
+			check!(
+				DecodeError {
+					position: len,
+					kind: DecodeKind::Padding
+				},
+				input.len() - len < group && len % group * bit % 8 < bit
+			);
+		}
+		let written = len * bit / 8;
+		ct::decode(bit, self.msb(), self.ctb(), self.val(), &input[..len], &mut output[..written])
+	}
+
+	/// Strips a trailing group made only of padding characters, if allowed
+	fn strip_trailing_pad_group<'a>(&self, input: &'a [u8]) -> &'a [u8] {
+		if !self.allow_trailing_pad_group() {
+			return input;
+		}
+		let pad = self.pad().unwrap_or(b'=');
+		match input.len().checked_sub(self.pad_group()) {
+			Some(len) if input[len..].iter().all(|&x| x == pad) => &input[..len],
+			_ => input,
+		}
+	}
+
+	fn wrap(&self) -> Option<(usize, &[u8])> {
+		match &self.0 {
+			InternalEncoding::Static(data) => {
+				if data.len() <= 514 {
+					return None;
+				}
+				let col = data[514] as usize;
+				if col == 0 {
+					return None;
+				}
+				let len = data[515] as usize;
+				Some((col, &data[516..516 + len]))
+			}
+			InternalEncoding::Owned(data) => {
+				let col = data[514] as usize;
+				if col == 0 {
+					return None;
 				}
 				let len = data[515] as usize;
 				Some((col, &data[516..516 + len]))
@@ -1508,11 +1991,10 @@ impl Encoding {
 	}
 
 	fn has_ignore(&self) -> bool {
-		let val = self.val();
-		for i in 0..128 {
-			if val[i] == IGNORE {
-				return true;
-			}
+		// This runs on every decode call. Without an early exit, the scan is vectorized.
+		#[allow(clippy::needless_bitwise_bool)]
+		if self.val().iter().fold(false, |acc, &x| acc | (x == IGNORE)) {
+			return true;
 		}
 		if let Some((_, end)) = self.wrap() {
 			return !end.is_empty();
@@ -1522,12 +2004,25 @@ impl Encoding {
 
 	/// Returns the maximum encoded length of an input of length `len`
 	///
-	/// See [`encode_mut`] for when to use it.
+	/// See [`encode_mut`] for when to use it. This is an upper bound: the number of bytes actually
+	/// written by [`encode_mut`] may be smaller, in particular for wrapped and arithmetic
+	/// encodings. Always slice the output with the returned `written` count.
 	///
 	/// # Errors
 	///
 	/// Returns an error if `len` is too large.
 	pub fn encode_len(&self, len: usize) -> Result<usize, EncodeError> {
+		if let Some(blocks) = self.arithmetic_block() {
+			let base = self.get_symbols().len();
+			let full = len / blocks.size * arithmetic::block_symbols(base, blocks.size);
+			let last = match len % blocks.size {
+				0 => 0,
+				rest => blocks.symbols(base, rest),
+			};
+			return full.checked_add(last).ok_or(EncodeError {
+				kind: EncodeKind::Overflow,
+			});
+		}
 		if self.is_arithmetic() {
 			// Upper bound for arithmetic encoding: ceil(len * 8 / log2(58)) approx 1.38 * len
 			// We use 3/2 as a safe upper bound factor plus 2 for rounding/leaders.
@@ -1559,19 +2054,37 @@ impl Encoding {
 
 	/// Encodes `input` in `output`
 	///
-	/// # Panics
+	/// Returns the number of bytes written, which is at most [`encode_len`] for the `input`
+	/// length. The `output` may be larger than needed (e.g. a reused buffer): only the first
+	/// `written` bytes are meaningful and the rest is left unspecified.
 	///
-	/// Panics if the `output` length is not the result of [`encode_len`] for the `input` length.
+	/// Arithmetic encodings (like [`BASE58`]) compute on a stack buffer of 512 bytes. Larger
+	/// inputs are computed on the heap with the `alloc` feature. Without it, they are limited to
+	/// 512 bytes, see [`encode_mut_n`] to change this limit.
 	///
 	/// # Errors
 	///
-	/// Returns an error if the `output` length is too small.
+	/// Returns an error if the `output` length is smaller than [`encode_len`] for the `input`
+	/// length. For arithmetic encodings without the `alloc` feature, returns an error of kind
+	/// [`Overflow`] if the input is longer than 512 bytes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let mut buffer = [0u8; 16];
+	/// let written = BASE64.encode_mut(b"Hello", &mut buffer).unwrap();
+	/// assert_eq!(&buffer[.. written], b"SGVsbG8=");
+	/// ```
+	///
+	/// [`encode_len`]: struct.Encoding.html#method.encode_len
+	/// [`encode_mut_n`]: struct.Encoding.html#method.encode_mut_n
+	/// [`BASE58`]: constant.BASE58.html
+	/// [`Overflow`]: enum.EncodeKind.html#variant.Overflow
 	#[allow(clippy::cognitive_complexity)]
 	pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
 		if self.is_arithmetic() {
-			// Utiliser l'encodage arithmétique
-			let symbols = self.get_symbols();
-			crate::arithmetic::encode_to_buffer(symbols, input, output)
+			self.encode_arithmetic::<{ arithmetic::MAX_BIGINT_BUFFER }>(input, output, true)
 		} else {
 			let len = self.encode_len(input.len())?;
 
@@ -1579,14 +2092,24 @@ impl Encoding {
 				EncodeError {
 					kind: EncodeKind::BufferTooSmall
 				},
-				output.len() == len
+				output.len() >= len
 			);
+			let output = &mut output[..len];
 
 			let bit = self.bit();
 			let msb = self.msb();
 			let pad_mode = self.pad_mode();
 			let pad = self.pad();
 			let wrap = self.wrap();
+			if self.constant_time() {
+				// Constant-time encodings have no wrapping and only standard padding.
+				let (data, padding) = output.split_at_mut((8 * input.len()).div_ceil(bit));
+				ct::encode(bit, msb, self.sym(), input, data);
+				if let Some(pad) = pad {
+					padding.fill(pad);
+				}
+				return Ok(len);
+			}
 
 			let written = dispatch! {
 				let bit: usize = bit;
@@ -1598,6 +2121,65 @@ impl Encoding {
 		}
 	}
 
+	/// Encodes `input` in `output` without allocating
+	///
+	/// This is the same as [`encode_mut`], except that arithmetic encodings compute on a stack
+	/// buffer of `N` 32-bit words and never allocate. The input is then limited to `4 * N` bytes.
+	/// This is meant for `no_std` targets, to adapt the limit to their stack.
+	///
+	/// # Errors
+	///
+	/// See [`encode_mut`]. For arithmetic encodings, returns an error of kind [`Overflow`] if
+	/// the input is longer than `4 * N` bytes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58, EncodeKind};
+	/// let input = [0xff; 600];
+	/// let mut output = [0u8; 1024];
+	/// let error = BASE58.encode_mut_n::<128>(&input, &mut output).unwrap_err();
+	/// assert_eq!(error.kind, EncodeKind::Overflow);
+	/// assert!(BASE58.encode_mut_n::<150>(&input, &mut output).is_ok());
+	/// ```
+	///
+	/// [`encode_mut`]: struct.Encoding.html#method.encode_mut
+	/// [`Overflow`]: enum.EncodeKind.html#variant.Overflow
+	pub fn encode_mut_n<const N: usize>(
+		&self,
+		input: &[u8],
+		output: &mut [u8],
+	) -> Result<usize, EncodeError> {
+		if self.is_arithmetic() {
+			self.encode_arithmetic::<N>(input, output, false)
+		} else {
+			self.encode_mut(input, output)
+		}
+	}
+
+	/// Encodes `input` in `output` for arithmetic encodings
+	///
+	/// The computation uses a stack buffer of `N` words, or the heap if `grow` is set.
+	fn encode_arithmetic<const N: usize>(
+		&self,
+		input: &[u8],
+		output: &mut [u8],
+		grow: bool,
+	) -> Result<usize, EncodeError> {
+		let symbols = self.get_symbols();
+		// A malformed table could have fewer than 2 symbols, which would divide by zero.
+		check!(
+			EncodeError {
+				kind: EncodeKind::Base
+			},
+			symbols.len() >= 2
+		);
+		if let Some(blocks) = self.arithmetic_block() {
+			return arithmetic::encode_blocks(symbols, blocks, input, output);
+		}
+		arithmetic::encode_to_buffer::<N>(symbols, input, output, grow)
+	}
+
 	/// Encodes `input` in `output` and returns it as a `&str`
 	///
 	/// It is guaranteed that `output` and the return value only differ by their type. They both
@@ -1639,32 +2221,101 @@ impl Encoding {
 		Encoder::new(self, output)
 	}
 
+	/// Returns a new decoder
+	///
+	/// See [`Decoder`] for how fragmented input is decoded.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let mut output = Vec::new();
+	/// let mut decoder = BASE64.new_decoder(&mut output);
+	/// for chunk in ["SGV", "sbG8gd", "29y", "bGQ="] {
+	///     decoder.append(chunk.as_bytes()).unwrap();
+	/// }
+	/// decoder.finalize().unwrap();
+	/// assert_eq!(output, b"Hello world");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn new_decoder<'a>(&'a self, output: &'a mut Vec<u8>) -> Decoder<'a> {
+		Decoder::new(self, output)
+	}
+
+	/// Returns the arithmetic encoding with its decoding table built once
+	///
+	/// Decoding with an arithmetic encoding (like [`BASE58`]) builds a table from symbols to
+	/// values on each call. This function builds it once, which matters when decoding many short
+	/// inputs. Returns `None` if the encoding is not arithmetic.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE58;
+	/// let base58 = BASE58.prepare_arithmetic().unwrap();
+	/// for input in ["2NEpo7TZRRrLZSi2U", "1112"] {
+	///     assert_eq!(base58.decode(input.as_bytes()), BASE58.decode(input.as_bytes()));
+	/// }
+	/// ```
+	///
+	/// [`BASE58`]: constant.BASE58.html
+	#[must_use]
+	pub fn prepare_arithmetic(&self) -> Option<PreparedArithmetic<'_>> {
+		let symbols = self.get_symbols();
+		if !self.is_arithmetic() || symbols.len() < 2 {
+			return None;
+		}
+		Some(PreparedArithmetic {
+			encoding: self,
+			lookup: self.arithmetic_lookup(),
+		})
+	}
+
+	/// Returns this encoding with a version prefix and a checksum
+	///
+	/// See [`BASE58CHECK`] for the usual instance.
+	///
+	/// [`BASE58CHECK`]: constant.BASE58CHECK.html
+	#[must_use]
+	pub const fn checked(&self) -> CheckedEncoding<'_> {
+		CheckedEncoding { encoding: self }
+	}
+
 	/// Encodes `input` in `output`
 	///
+	/// Arithmetic encodings (like [`BASE58`]) cannot be split in blocks and are encoded at once
+	/// on the heap, unless they use [`arithmetic_block_size`].
+	///
 	/// # Errors
 	///
 	/// Returns an error if the `output` is not writable.
+	///
+	/// [`BASE58`]: constant.BASE58.html
+	/// [`arithmetic_block_size`]: struct.Specification.html#structfield.arithmetic_block_size
 	#[cfg(feature = "std")]
 	pub fn encode_write(
 		&self,
 		input: &[u8],
 		mut output: impl std::io::Write,
 	) -> std::io::Result<()> {
-		let mut buffer = [0u8; 1024];
-		for chunk in
-			input.chunks(floor(1024 / self.block_len().1 * self.block_len().0, self.block_len().0))
-		{
-			let len = self
-				.encode_mut(chunk, &mut buffer[..self.encode_len(chunk.len()).unwrap()])
-				.unwrap();
-			output.write_all(&buffer[..len])?;
+		if self.is_arithmetic() && self.arithmetic_block().is_none() {
+			return output.write_all(&self.encode_to_vec(input));
 		}
-		Ok(())
+		self.encode_write_buffer(input, output, &mut Scratch([0u8; 1024])[..])
 	}
 
 	/// Encodes `input` in `output` through a buffer
 	///
 	/// This function uses a buffer to avoid many small writes to `output`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the `output` is not writable. Returns an error of kind
+	/// [`InvalidInput`] if the buffer cannot hold a single block, or the whole output for
+	/// arithmetic encodings.
+	///
+	/// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
 	#[cfg(feature = "std")]
 	pub fn encode_write_buffer(
 		&self,
@@ -1672,15 +2323,32 @@ impl Encoding {
 		mut output: impl std::io::Write,
 		buffer: &mut [u8],
 	) -> std::io::Result<()> {
-		let (ilen, olen) = self.block_len();
-		let max_ilen = floor(buffer.len() / olen * ilen, ilen);
+		if input.is_empty() {
+			return Ok(());
+		}
+		let max_ilen = if let Some((ilen, olen)) = self.arithmetic_block_len() {
+			floor(buffer.len() / olen * ilen, ilen)
+		} else if self.is_arithmetic() {
+			// Arithmetic encodings have no block structure (and a zero bit width).
+			input.len()
+		} else {
+			let (ilen, olen) = self.block_len();
+			debug_assert!(ilen >= 1 && olen >= 1);
+			floor(buffer.len() / olen.max(1) * ilen, ilen.max(1))
+		};
 		if max_ilen == 0 {
 			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "buffer too small"));
 		}
+		let invalid = |error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error);
 		for chunk in input.chunks(max_ilen) {
-			let len = self
-				.encode_mut(chunk, &mut buffer[..self.encode_len(chunk.len()).unwrap()])
-				.unwrap();
+			let olen = self.encode_len(chunk.len()).map_err(invalid)?;
+			if olen > buffer.len() {
+				return Err(std::io::Error::new(
+					std::io::ErrorKind::InvalidInput,
+					"buffer too small",
+				));
+			}
+			let len = self.encode_mut(chunk, &mut buffer[..olen]).map_err(invalid)?;
 			output.write_all(&buffer[..len])?;
 		}
 		Ok(())
@@ -1695,23 +2363,236 @@ impl Encoding {
 		}
 	}
 
+	/// Returns a displayable version of `input` using a buffer of `N` bytes
+	///
+	/// This is like [`encode_display`], which uses 1024 bytes, but for tight stacks. Formatting
+	/// fails if `N` bytes cannot hold a single block, which is a whole line for wrapped encodings.
+	/// Arithmetic encodings whose output does not fit are encoded on the heap with the `alloc`
+	/// feature and fail without it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let display = BASE64.encode_display_n::<8>(b"Hello world");
+	/// assert_eq!(format!("{}", display), "SGVsbG8gd29ybGQ=");
+	/// ```
+	///
+	/// [`encode_display`]: struct.Encoding.html#method.encode_display
+	#[must_use]
+	pub fn encode_display_n<'a, const N: usize>(&'a self, input: &'a [u8]) -> Display<'a, N> {
+		Display {
+			encoding: self,
+			input,
+		}
+	}
+
+	/// Returns a displayable version of `input` encoded through `scratch`
+	///
+	/// This is like [`encode_display`] but the caller controls the size of the intermediate buffer.
+	/// The input is encoded in the largest chunks that fit in `scratch`. Formatting fails if
+	/// `scratch` cannot hold a single block, which is a whole line for wrapped encodings.
+	/// Arithmetic encodings whose output does not fit are encoded on the heap with the `alloc`
+	/// feature and fail without it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let mut scratch = [0u8; 8];
+	/// let display = BASE64.encode_display_buffered(b"Hello world", &mut scratch);
+	/// assert_eq!(format!("{}", display), "SGVsbG8gd29ybGQ=");
+	/// ```
+	///
+	/// [`encode_display`]: struct.Encoding.html#method.encode_display
+	#[must_use]
+	pub fn encode_display_buffered<'a>(
+		&'a self,
+		input: &'a [u8],
+		scratch: &'a mut [u8],
+	) -> impl core::fmt::Display + 'a {
+		DisplayBuffered {
+			encoding: self,
+			input,
+			scratch: core::cell::RefCell::new(scratch),
+		}
+	}
+
+	/// Encodes `input` chunk by chunk
+	///
+	/// The input is encoded through a 1024 bytes stack buffer and `f` is called with each encoded
+	/// chunk, in order. The concatenation of the chunks is the same as [`encode`], but nothing is
+	/// allocated. Chunks are block-aligned (and line-aligned when wrapping), except for the last.
+	/// Arithmetic encodings are not chunked and their output is allocated if it does not fit.
+	///
+	/// # Panics
+	///
+	/// Panics without the `alloc` feature for arithmetic encodings if the encoded input does not
+	/// fit in 1024 bytes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let mut output = String::new();
+	/// BASE64.encode_chunks(b"Hello world", |chunk| output.push_str(chunk));
+	/// assert_eq!(output, "SGVsbG8gd29ybGQ=");
+	/// ```
+	///
+	/// [`encode`]: struct.Encoding.html#method.encode
+	pub fn encode_chunks(&self, input: &[u8], f: impl FnMut(&str)) {
+		encode_fmt(self, input, &mut Scratch([0u8; 1024])[..], &mut ChunkSink(f))
+			.expect("encoding failed");
+	}
+
 	/// Returns encoded `input`
 	#[cfg(feature = "alloc")]
 	#[must_use]
 	pub fn encode(&self, input: &[u8]) -> String {
+		let output = self.encode_to_vec(input);
+		safety_assert!(output.is_ascii());
+		// SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
+		unsafe { String::from_utf8_unchecked(output) }
+	}
+
+	/// Returns encoded `input` as raw ASCII bytes
+	///
+	/// This is the same as [`encode`] without the `String` wrapper.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// assert_eq!(BASE64.encode_to_vec(b"Hello"), b"SGVsbG8=");
+	/// ```
+	///
+	/// [`encode`]: struct.Encoding.html#method.encode
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn encode_to_vec(&self, input: &[u8]) -> Vec<u8> {
 		let len = self.encode_len(input.len()).expect("encoding length overflow");
 		let mut output = vec![0u8; len];
 		let written = self.encode_mut(input, &mut output).expect("encoding failed");
 		output.truncate(written);
+		output
+	}
+
+	/// Returns encoded `input`, computed in parallel
+	///
+	/// The input is split at block boundaries (whole lines when wrapping) in chunks of about 64
+	/// kilobytes, which are encoded on the [rayon] thread pool. The output is the same as
+	/// [`encode`]. Arithmetic encodings are not split and encode sequentially.
+	///
+	/// # Panics
+	///
+	/// Panics if the encoding fails (e.g. length overflow).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::HEXLOWER;
+	/// let input = vec![0xca; 1 << 20];
+	/// assert_eq!(HEXLOWER.par_encode(&input), HEXLOWER.encode(&input));
+	/// ```
+	///
+	/// [rayon]: https://crates.io/crates/rayon
+	/// [`encode`]: struct.Encoding.html#method.encode
+	#[cfg(feature = "rayon")]
+	#[must_use]
+	pub fn par_encode(&self, input: &[u8]) -> String {
+		use rayon::prelude::*;
+		if self.is_arithmetic() {
+			return self.encode(input);
+		}
+		let (ilen, olen) = self.block_len();
+		let blocks = (PAR_CHUNK / ilen).max(1);
+		let (ichunk, ochunk) = (blocks * ilen, blocks * olen);
+		let (head, tail) = input.split_at(input.len() / ichunk * ichunk);
+		let start = head.len() / ichunk * ochunk;
+		let len = self.encode_len(tail.len()).expect("encoding length overflow");
+		let mut output = vec![0u8; start + len];
+		let (left, right) = output.split_at_mut(start);
+		// Whole blocks are encoded without padding, so each chunk fills its output exactly.
+		head.par_chunks(ichunk).zip(left.par_chunks_mut(ochunk)).for_each(|(input, output)| {
+			let written = self.encode_mut(input, output).expect("encoding failed");
+			safety_assert!(written == output.len());
+		});
+		let written = self.encode_mut(tail, right).expect("encoding failed");
+		output.truncate(start + written);
 		safety_assert!(output.is_ascii());
 		// SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
 		unsafe { String::from_utf8_unchecked(output) }
 	}
 
+	/// Returns encoded `input` as a fixed-size array
+	///
+	/// This encodes small fixed-size inputs, like hashes, without allocating. The output length
+	/// `M` must be the encoded length of `N` bytes (see [`encode_len`]). Arithmetic encodings have
+	/// a variable output length, so the output must have exactly `M` symbols.
+	///
+	/// # Errors
+	///
+	/// Returns an error of kind [`Length`] if `M` does not match the encoded length.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::HEXLOWER;
+	/// assert_eq!(HEXLOWER.encode_array::<2, 4>(&[0xbe, 0xef]).unwrap(), *b"beef");
+	/// assert!(HEXLOWER.encode_array::<2, 3>(&[0xbe, 0xef]).is_err());
+	/// ```
+	///
+	/// [`encode_len`]: struct.Encoding.html#method.encode_len
+	/// [`Length`]: enum.EncodeKind.html#variant.Length
+	pub fn encode_array<const N: usize, const M: usize>(
+		&self,
+		input: &[u8; N],
+	) -> Result<[u8; M], EncodeError> {
+		let length = EncodeError {
+			kind: EncodeKind::Length,
+		};
+		if !self.is_arithmetic() {
+			check!(length, self.encode_len(N)? == M);
+		}
+		let mut output = [0u8; M];
+		let written = self.encode_mut(input, &mut output).map_err(|error| match error.kind {
+			EncodeKind::BufferTooSmall => length,
+			_ => error,
+		})?;
+		check!(length, written == M);
+		Ok(output)
+	}
+
+	/// Returns encoded `input`, borrowing when no allocation is needed
+	///
+	/// An empty `input` always encodes to the empty string, which is returned as
+	/// `Cow::Borrowed("")` without allocating. Any other input is encoded with [`encode`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::borrow::Cow;
+	/// use data_encoding::BASE64;
+	/// assert_eq!(BASE64.encode_cow(b""), Cow::Borrowed(""));
+	/// assert_eq!(BASE64.encode_cow(b"Hello"), "SGVsbG8=");
+	/// ```
+	///
+	/// [`encode`]: struct.Encoding.html#method.encode
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn encode_cow<'a>(&self, input: &'a [u8]) -> Cow<'a, str> {
+		if input.is_empty() {
+			return Cow::Borrowed("");
+		}
+		Cow::Owned(self.encode(input))
+	}
+
 	/// Returns the maximum decoded length of an input of length `len`
 	///
 	/// See [`decode_mut`] for when to use it. In particular, the actual decoded length might be
-	/// smaller if the actual input contains padding or ignored characters.
+	/// smaller if the actual input contains padding or ignored characters. For arithmetic
+	/// encodings, this is `len` because leading zero symbols decode to one byte each. Use
+	/// [`decode_len_exact`] to size buffers for a given input.
 	///
 	/// # Panics
 	///
@@ -1737,14 +2618,20 @@ impl Encoding {
 	/// # Errors
 	///
 	/// Returns an error if `len` is invalid. The error kind is [`Length`] and the [position] is the
-	/// greatest valid input length.
+	/// greatest valid input length. Without padding, a length is invalid if its last partial block
+	/// would have a symbol made only of trailing bits, like an odd number of hexadecimal symbols.
 	///
 	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`decode_len_exact`]: struct.Encoding.html#method.decode_len_exact
 	/// [`Length`]: enum.DecodeKind.html#variant.Length
 	/// [position]: struct.DecodeError.html#structfield.position
 	pub fn decode_len(&self, len: usize) -> Result<usize, DecodeError> {
+		if let Some(blocks) = self.arithmetic_block() {
+			return Ok(self.arithmetic_block_decode_len(blocks, len));
+		}
 		if self.is_arithmetic() {
-			// Safe upper bound for arithmetic decoding.
+			// Safe upper bound for arithmetic decoding: an input made only of zero symbols decodes
+			// to one byte per symbol. Callers that have the input can use decode_len_exact.
 			return Ok(len);
 		} else {
 			let bit = self.bit();
@@ -1769,18 +2656,149 @@ impl Encoding {
 		}
 	}
 
-	/// Decodes `input` in `output`
+	/// Returns the decoded length of an input with a known number of ignored characters
 	///
-	/// Returns the length of the decoded output. This length may be smaller than the output length
-	/// if the input contained padding or ignored characters. The output bytes after the returned
-	/// length are not initialized and should not be read.
+	/// This is a tighter estimate than [`decode_len`] when the caller already counted the ignored
+	/// characters of the input, for example the line separators of wrapped input or the `ignored`
+	/// field of a previous [`decode_mut_full`]. Only `input_len - ignored` characters are considered
+	/// significant. Note that [`decode_mut`] still expects an output of [`decode_len`] bytes, so
+	/// this is meant for sizing buffers that are filled incrementally.
 	///
-	/// # Panics
+	/// # Errors
 	///
-	/// Panics if the `output` length does not match the result of [`decode_len`] for the `input`
-	/// length. Also panics if `decode_len` fails for the `input` length.
+	/// Returns an error if `ignored` is greater than `input_len` (with [position] `input_len`) or if
+	/// the number of significant characters is invalid (as in [`decode_len`]).
 	///
-	/// # Errors
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64_MIME;
+	/// assert_eq!(BASE64_MIME.decode_len(10).unwrap(), 6);
+	/// assert_eq!(BASE64_MIME.decode_len_hint(10, 2).unwrap(), 6);
+	/// assert_eq!(BASE64_MIME.decode_len(100).unwrap(), 75);
+	/// assert_eq!(BASE64_MIME.decode_len_hint(100, 4).unwrap(), 72);
+	/// ```
+	///
+	/// [`decode_len`]: struct.Encoding.html#method.decode_len
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`decode_mut_full`]: struct.Encoding.html#method.decode_mut_full
+	/// [position]: struct.DecodeError.html#structfield.position
+	pub fn decode_len_hint(&self, input_len: usize, ignored: usize) -> Result<usize, DecodeError> {
+		let len = input_len.checked_sub(ignored).ok_or(DecodeError {
+			position: input_len,
+			kind: DecodeKind::Length,
+		})?;
+		if let Some(blocks) = self.arithmetic_block() {
+			return Ok(self.arithmetic_block_decode_len(blocks, len));
+		}
+		if self.is_arithmetic() {
+			return Ok(len);
+		}
+		let bit = self.bit();
+		let pad_mode = self.pad_mode();
+		let (ilen, olen) = dispatch! {
+			let bit: usize = bit;
+			let pad_mode: PaddingMode = pad_mode;
+			decode_wrap_len(bit.val(), pad_mode.val(), len)
+		}
+		.ok_or(DecodeError {
+			position: 0,
+			kind: DecodeKind::Overflow,
+		})?;
+		check!(
+			DecodeError {
+				position: ilen,
+				kind: DecodeKind::Length
+			},
+			len == ilen
+		);
+		Ok(olen)
+	}
+
+	/// Returns the exact decoded length of `input`
+	///
+	/// Unlike [`decode_len`], this reads the input. For positional encodings, ignored characters
+	/// and padding are not counted. For arithmetic encodings, leading zero symbols count as one
+	/// byte each and the rest as the bytes of its value, which needs the same big integer
+	/// computation as decoding (except in block mode). This is the length returned by
+	/// [`decode_mut`] if the input is valid, so it can size buffers exactly, for example the
+	/// output of [`decode_mut`] for arithmetic encodings. This function is not constant-time.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the input length is invalid, like [`decode_len`], or if a character is
+	/// neither a symbol, ignored, nor padding. Other errors, like non-zero trailing bits, are only
+	/// detected when decoding. Arithmetic encodings may also fail like [`decode_mut`] with
+	/// [`Overflow`] without the `alloc` feature.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58, BASE64_MIME};
+	/// assert_eq!(BASE64_MIME.decode_len(10).unwrap(), 6);
+	/// assert_eq!(BASE64_MIME.decode_len_exact(b"Zm9v\r\nYg==").unwrap(), 4);
+	/// assert_eq!(BASE58.decode_len(8).unwrap(), 8);
+	/// assert_eq!(BASE58.decode_len_exact(b"1Cn8eVZg").unwrap(), 6);
+	/// ```
+	///
+	/// [`decode_len`]: struct.Encoding.html#method.decode_len
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`Overflow`]: enum.DecodeKind.html#variant.Overflow
+	pub fn decode_len_exact(&self, input: &[u8]) -> Result<usize, DecodeError> {
+		if self.is_arithmetic() {
+			let symbols = self.get_symbols();
+			check!(
+				DecodeError {
+					position: 0,
+					kind: DecodeKind::Base
+				},
+				symbols.len() >= 2
+			);
+			let lookup = self.arithmetic_lookup();
+			return match self.arithmetic_block() {
+				Some(blocks) => arithmetic::decoded_blocks_len(symbols, &lookup, blocks, input),
+				None => arithmetic::decoded_len::<{ arithmetic::MAX_BIGINT_BUFFER }>(
+					symbols, &lookup, input, true,
+				),
+			};
+		}
+		let _ = self.decode_len(input.len())?;
+		let bit = self.bit();
+		let val = self.val();
+		// Padding ends a group, whose partial byte is dropped.
+		let mut len = 0;
+		let mut count = 0;
+		for (position, &x) in input.iter().enumerate() {
+			match val.get(x as usize).copied().unwrap_or(INVALID) {
+				IGNORE => (),
+				PADDING => {
+					len += count * bit / 8;
+					count = 0;
+				}
+				INVALID => {
+					return Err(DecodeError {
+						position,
+						kind: DecodeKind::Symbol,
+					});
+				}
+				_ => count += 1,
+			}
+		}
+		Ok(len + count * bit / 8)
+	}
+
+	/// Decodes `input` in `output`
+	///
+	/// Returns the length of the decoded output. This length may be smaller than the output length
+	/// if the input contained padding or ignored characters. The output bytes after the returned
+	/// length are not initialized and should not be read.
+	///
+	/// # Panics
+	///
+	/// Panics if the `output` length does not match the result of [`decode_len`] for the `input`
+	/// length. Also panics if `decode_len` fails for the `input` length.
+	///
+	/// # Errors
 	///
 	/// Returns an error if `input` is invalid. See [`decode`] for more details. The are two
 	/// differences though:
@@ -1790,6 +2808,11 @@ impl Encoding {
 	/// - The [`read`] first bytes of the input have been successfully decoded to the [`written`]
 	///   first bytes of the output.
 	///
+	/// Arithmetic encodings (like [`BASE58`]) decode on a stack buffer of 512 bytes. Larger values
+	/// are computed on the heap with the `alloc` feature. Without it, the decoded value (without
+	/// its leading zeros) is limited to 512 bytes and the error kind is [`Overflow`] above that,
+	/// see [`decode_mut_n`] to change this limit.
+	///
 	/// # Examples
 	///
 	/// ```rust
@@ -1806,27 +2829,13 @@ impl Encoding {
 	/// [`Length`]: enum.DecodeKind.html#variant.Length
 	/// [`read`]: struct.DecodePartial.html#structfield.read
 	/// [`written`]: struct.DecodePartial.html#structfield.written
+	/// [`BASE58`]: constant.BASE58.html
+	/// [`Overflow`]: enum.DecodeKind.html#variant.Overflow
+	/// [`decode_mut_n`]: struct.Encoding.html#method.decode_mut_n
 	#[allow(clippy::cognitive_complexity)]
 	pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
 		if self.is_arithmetic() {
-			// Utiliser le décodage arithmétique
-			let symbols = self.get_symbols();
-			let input_str = core::str::from_utf8(input).map_err(|e| DecodePartial {
-				read: e.valid_up_to(),
-				written: 0,
-				error: DecodeError {
-					position: e.valid_up_to(),
-					kind: DecodeKind::Symbol,
-				},
-			})?;
-			match crate::arithmetic::decode_to_buffer(symbols, input_str, output) {
-				Ok(len) => Ok(len),
-				Err(e) => Err(DecodePartial {
-					read: e.position,
-					written: 0,
-					error: e,
-				}),
-			}
+			self.decode_arithmetic::<{ arithmetic::MAX_BIGINT_BUFFER }>(input, output, true)
 		} else {
 			let len = self.decode_len(input.len()).map_err(|e| DecodePartial {
 				read: 0,
@@ -1844,10 +2853,23 @@ impl Encoding {
 				},
 				output.len() == len
 			);
+			let input = self.strip_trailing_pad_group(input);
 			let bit = self.bit();
 			let msb = self.msb();
 			let pad_mode = self.pad_mode();
 			let has_ignore = self.has_ignore();
+			if self.constant_time() {
+				return self.decode_constant_time(input, output).map_err(|error| DecodePartial {
+					read: 0,
+					written: 0,
+					error,
+				});
+			}
+			if input.len() <= 16 && !has_ignore {
+				if let Some(written) = decode_small(self, bit, msb, input, output) {
+					return Ok(written);
+				}
+			}
 			let written = dispatch! {
 				let bit: usize = bit;
 				let msb: bool = msb;
@@ -1861,7 +2883,6 @@ impl Encoding {
 					self.ctb(),
 					self.val(),
 					self.sym(),
-					self.pad(),
 					input,
 					output,
 				)
@@ -1870,6 +2891,186 @@ impl Encoding {
 		}
 	}
 
+	/// Decodes `input` in `output` without allocating
+	///
+	/// This is the same as [`decode_mut`], except that arithmetic encodings compute on a stack
+	/// buffer of `N` 32-bit words and never allocate. The decoded value (without its leading
+	/// zeros) is then limited to `4 * N` bytes. This is meant for `no_std` targets, to adapt the
+	/// limit to their stack.
+	///
+	/// # Panics
+	///
+	/// See [`decode_mut`].
+	///
+	/// # Errors
+	///
+	/// See [`decode_mut`]. For arithmetic encodings, returns an error of kind [`Overflow`] if
+	/// the decoded value is longer than `4 * N` bytes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58, DecodeKind};
+	/// let input = [b'z'; 16];
+	/// let mut output = [0u8; 16];
+	/// let error = BASE58.decode_mut_n::<2>(&input, &mut output).unwrap_err();
+	/// assert_eq!(error.error.kind, DecodeKind::Overflow);
+	/// assert_eq!(BASE58.decode_mut_n::<4>(&input, &mut output), Ok(12));
+	/// ```
+	///
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`Overflow`]: enum.DecodeKind.html#variant.Overflow
+	pub fn decode_mut_n<const N: usize>(
+		&self,
+		input: &[u8],
+		output: &mut [u8],
+	) -> Result<usize, DecodePartial> {
+		if self.is_arithmetic() {
+			self.decode_arithmetic::<N>(input, output, false)
+		} else {
+			self.decode_mut(input, output)
+		}
+	}
+
+	/// Decodes `input` in `output` for arithmetic encodings
+	///
+	/// The computation uses a stack buffer of `N` words, or the heap if `grow` is set.
+	fn decode_arithmetic<const N: usize>(
+		&self,
+		input: &[u8],
+		output: &mut [u8],
+		grow: bool,
+	) -> Result<usize, DecodePartial> {
+		let symbols = self.get_symbols();
+		check!(
+			DecodePartial {
+				read: 0,
+				written: 0,
+				error: DecodeError {
+					position: 0,
+					kind: DecodeKind::Base,
+				},
+			},
+			symbols.len() >= 2
+		);
+		let input = core::str::from_utf8(input).map_err(|e| DecodePartial {
+			read: e.valid_up_to(),
+			written: 0,
+			error: DecodeError {
+				position: e.valid_up_to(),
+				kind: DecodeKind::Symbol,
+			},
+		})?;
+		let lookup = self.arithmetic_lookup();
+		let result = match self.arithmetic_block() {
+			Some(blocks) => {
+				arithmetic::decode_blocks(symbols, &lookup, blocks, input.as_bytes(), output)
+			}
+			None => arithmetic::decode_with_lookup::<N>(
+				symbols,
+				&lookup,
+				input.as_bytes(),
+				output,
+				grow,
+			),
+		};
+		result.map_err(|e| DecodePartial {
+			read: e.position,
+			written: 0,
+			error: e,
+		})
+	}
+
+	/// Decodes `input` in `output` without validating it
+	///
+	/// Returns the length of the decoded output. This is a faster version of [`decode_mut`] for
+	/// inputs known to be valid, for example because they were produced by [`encode`] from the same
+	/// encoding. Symbols, trailing bits, and padding are not checked.
+	///
+	/// # Safety
+	///
+	/// The `input` must be valid for this encoding, in other words [`decode_mut`] must succeed on
+	/// it. In particular, the length must be valid for [`decode_len`], which rejects unpadded
+	/// inputs ending with a symbol made only of trailing bits. Calling this function on invalid
+	/// input is undefined behavior.
+	///
+	/// # Panics
+	///
+	/// Panics if the `output` length is smaller than the result of [`decode_len`] for the `input`
+	/// length.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// # let mut buffer = vec![0; 100];
+	/// let input = b"SGVsbA==byB3b3JsZA==";
+	/// let output = &mut buffer[0 .. BASE64.decode_len(input.len()).unwrap()];
+	/// let len = unsafe { BASE64.decode_mut_unchecked(input, output) };
+	/// assert_eq!(&output[0 .. len], b"Hello world");
+	/// ```
+	///
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`encode`]: struct.Encoding.html#method.encode
+	/// [`decode_len`]: struct.Encoding.html#method.decode_len
+	#[cfg(feature = "unsafe-unchecked")]
+	pub unsafe fn decode_mut_unchecked(&self, input: &[u8], output: &mut [u8]) -> usize {
+		if self.is_arithmetic() {
+			return match self.decode_mut(input, output) {
+				Ok(written) => written,
+				Err(_) => unsafe { core::hint::unreachable_unchecked() },
+			};
+		}
+		let bit = self.bit();
+		let msb = self.msb();
+		let has_ignore = self.has_ignore();
+		dispatch! {
+			let bit: usize = bit;
+			let msb: bool = msb;
+			let has_ignore: bool = has_ignore;
+			unsafe { decode_unchecked_mut(bit, msb, has_ignore, self.val(), input, output) }
+		}
+	}
+
+	/// Decodes `input` in `output` and counts the ignored characters
+	///
+	/// This is like [`decode_mut`], but also reports how many characters of `input` were ignored,
+	/// for example to validate the line structure of wrapped input.
+	///
+	/// # Panics
+	///
+	/// Panics in the same cases as [`decode_mut`].
+	///
+	/// # Errors
+	///
+	/// Returns an error in the same cases as [`decode_mut`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64_MIME;
+	/// let input = b"Zm9v\r\nYmFy";
+	/// let mut output = vec![0; BASE64_MIME.decode_len(input.len()).unwrap()];
+	/// let outcome = BASE64_MIME.decode_mut_full(input, &mut output).unwrap();
+	/// assert_eq!((outcome.written, outcome.ignored), (6, 2));
+	/// ```
+	///
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	pub fn decode_mut_full(
+		&self,
+		input: &[u8],
+		output: &mut [u8],
+	) -> Result<DecodeOutcome, DecodePartial> {
+		let written = self.decode_mut(input, output)?;
+		let ignored = if self.is_arithmetic() {
+			0
+		} else {
+			let val = self.val();
+			input.iter().filter(|&&x| x < 128 && val[x as usize] == IGNORE).count()
+		};
+		Ok(DecodeOutcome { written, ignored })
+	}
+
 	/// Returns decoded `input`
 	///
 	/// # Errors
@@ -1901,78 +3102,1187 @@ impl Encoding {
 	/// [position]: struct.DecodeError.html#structfield.position
 	#[cfg(feature = "alloc")]
 	pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
-		let mut output = vec![0u8; self.decode_len(input.len())?];
+		let len = if self.is_arithmetic() {
+			self.arithmetic_decode_len(input)
+		} else {
+			self.decode_len(input.len())?
+		};
+		let mut output = vec![0u8; len];
 		let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
 		output.truncate(len);
 		Ok(output)
 	}
 
-	/// Returns the bit-width
-	#[must_use]
-	pub fn bit_width(&self) -> usize {
-		if self.is_arithmetic() {
-			let base = self.data()[512] as u32;
-			if base == 0 {
-				return 0;
-			}
-			(32 - base.leading_zeros()) as usize
+	/// Appends the decoding of `input` to `output`
+	///
+	/// This reuses the capacity of `output` across calls, like [`encode_append`] does for
+	/// encoding.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid, see [`decode`] for more details. The length of
+	/// `output` is then unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let mut output = b"Hello".to_vec();
+	/// BASE64.decode_append(b"IHdvcmxk", &mut output).unwrap();
+	/// assert_eq!(output, b"Hello world");
+	/// assert!(BASE64.decode_append(b"IHdvcmx*", &mut output).is_err());
+	/// assert_eq!(output, b"Hello world");
+	/// ```
+	///
+	/// [`encode_append`]: struct.Encoding.html#method.encode_append
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "alloc")]
+	pub fn decode_append(&self, input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+		let len = if self.is_arithmetic() {
+			self.arithmetic_decode_len(input)
 		} else {
-			self.bit()
+			self.decode_len(input.len())?
+		};
+		let output_len = output.len();
+		output.resize(output_len + len, 0);
+		match self.decode_mut(input, &mut output[output_len..]) {
+			Ok(written) => {
+				output.truncate(output_len + written);
+				Ok(())
+			}
+			Err(partial) => {
+				output.truncate(output_len);
+				Err(partial.error)
+			}
 		}
 	}
 
-	/// Returns whether the encoding is canonical
+	/// Returns decoded `input` if it is exactly `expected` bytes long
 	///
-	/// An encoding is canonical if:
+	/// This is useful for fields of fixed size, like keys or hashes.
 	///
-	/// - trailing bits are zero
-	/// - no characters are ignored
-	/// - no characters are translated
-	/// - for padded encodings, there is no trailing padding
+	/// # Errors
 	///
-	/// In other words, an encoding is canonical if all inputs that could be produced by the
-	/// encoding function are successfully decoded, and all other inputs are rejected.
+	/// Returns an error if `input` is invalid, see [`decode`] for more details. Returns a
+	/// [`Length`] error at position `input.len()` if the decoded output is not `expected` bytes
+	/// long.
 	///
-	/// Canonical encodings are useful for hashing.
+	/// # Examples
 	///
-	/// A non-canonical encoding can be made canonical if:
+	/// ```rust
+	/// use data_encoding::{DecodeKind, BASE64};
+	/// assert_eq!(BASE64.decode_exact(b"SGVsbG8=", 5).unwrap(), b"Hello");
+	/// assert_eq!(BASE64.decode_exact(b"SGVsbG8=", 32).unwrap_err().kind, DecodeKind::Length);
+	/// ```
 	///
-	/// - trailing bits are checked
-	/// - padding is used
-	/// - characters are ignored
-	/// - characters are translated
-	#[must_use]
-	pub fn is_canonical(&self) -> bool {
-		if !self.ctb() {
-			return false;
-		}
-		let symbols = self.get_symbols();
-		let num_symbols = symbols.len();
-		if num_symbols == 0 {
-			return false;
-		}
-		let sym = self.sym();
-		let val = self.val();
-		for i in 0..256 {
-			if i >= val.len() || val[i] == INVALID {
-				continue;
-			}
-			if (val[i] as usize) >= num_symbols {
-				return false;
-			}
-			if sym[val[i] as usize] as usize != i {
-				return false;
-			}
-		}
-		true
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	#[cfg(feature = "alloc")]
+	pub fn decode_exact(&self, input: &[u8], expected: usize) -> Result<Vec<u8>, DecodeError> {
+		let output = self.decode(input)?;
+		let length = DecodeError {
+			position: input.len(),
+			kind: DecodeKind::Length,
+		};
+		check!(length, output.len() == expected);
+		Ok(output)
 	}
 
-	/// Returns the encoding specification
-	#[allow(clippy::missing_panics_doc)] // no panic
+	/// Returns the positions of all padding characters in `input`
+	///
+	/// The input is not decoded, so padding characters are reported wherever they are. For
+	/// example, a caller can check that they form a single trailing run. Encodings without padding
+	/// always return an empty vector.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// assert_eq!(BASE64.padding_positions(b"AB=C=="), [2, 4, 5]);
+	/// ```
 	#[cfg(feature = "alloc")]
 	#[must_use]
-	pub fn specification(&self) -> Specification {
-		let mut specification = Specification::new();
+	pub fn padding_positions(&self, input: &[u8]) -> Vec<usize> {
+		let val = self.val();
+		let is_padding = |x: u8| x < 128 && val[x as usize] == PADDING;
+		input.iter().enumerate().filter(|&(_, &x)| is_padding(x)).map(|(i, _)| i).collect()
+	}
+
+	/// Upper bound of the decoded length of `input` for arithmetic encodings
+	///
+	/// Leading zero symbols decode to one byte each, and the other symbols to about
+	/// `log(base) / log(256)` bytes each. This is tighter than [`decode_len`], which only knows
+	/// the input length and must assume it is made of zero symbols.
+	///
+	/// [`decode_len`]: struct.Encoding.html#method.decode_len
+	#[cfg(feature = "alloc")]
+	fn arithmetic_decode_len(&self, input: &[u8]) -> usize {
+		if let Some(blocks) = self.arithmetic_block() {
+			return self.arithmetic_block_decode_len(blocks, input.len());
+		}
+		let symbols = self.get_symbols();
+		if symbols.len() < 2 {
+			return input.len();
+		}
+		let leaders = input.iter().take_while(|&&x| x == symbols[0]).count();
+		leaders + arithmetic::value_len(symbols.len(), input.len() - leaders)
+	}
+
+	/// Upper bound of the decoded length of `len` symbols for arithmetic encodings in block mode
+	///
+	/// A last group too short for a byte counts as none, and is rejected when decoding. With a
+	/// zero-block symbol, each symbol may decode to a whole block.
+	fn arithmetic_block_decode_len(&self, blocks: arithmetic::Blocks, len: usize) -> usize {
+		if blocks.zero.is_some() {
+			return len.saturating_mul(blocks.size);
+		}
+		let base = self.get_symbols().len();
+		let group = arithmetic::block_symbols(base, blocks.size);
+		let rest = (1..blocks.size).take_while(|&n| blocks.symbols(base, n) <= len % group);
+		len / group * blocks.size + rest.count()
+	}
+
+	/// Returns decoded `input` or an error with the surrounding input
+	///
+	/// This is the same as [`decode`] but the error also holds up to 8 bytes of input on each side
+	/// of the error position, as well as the byte at that position. This is meant for error
+	/// messages, where the position alone is hard to act upon.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode`] for more details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let error = BASE64.decode_verbose(b"SGVsbG8*d29ybGQ=").unwrap_err();
+	/// assert_eq!(error.error.position, 7);
+	/// assert_eq!(error.window, b"SGVsbG8*d29ybGQ=");
+	/// assert_eq!(error.byte, Some(b'*'));
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "alloc")]
+	pub fn decode_verbose<'a>(&self, input: &'a [u8]) -> Result<Vec<u8>, DecodeErrorContext<'a>> {
+		self.decode(input).map_err(|error| DecodeErrorContext::new(input, error))
+	}
+
+	/// Returns decoded `input`
+	///
+	/// This is the same as [`decode`] for text. When the encoding ignores no characters, a
+	/// non-ASCII character is rejected before decoding, as a [`Symbol`] error at its (byte)
+	/// position in `input`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode`] for more details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64, DecodeKind};
+	/// assert_eq!(BASE64.decode_str("SGVsbG8=").unwrap(), b"Hello");
+	/// let error = BASE64.decode_str("SGé=").unwrap_err();
+	/// assert_eq!((error.position, error.kind), (2, DecodeKind::Symbol));
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	#[cfg(feature = "alloc")]
+	pub fn decode_str(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+		if let Some(error) = self.non_ascii_error(input) {
+			return Err(error);
+		}
+		self.decode(input.as_bytes())
+	}
+
+	/// Decodes `input` in `output`
+	///
+	/// This is the same as [`decode_mut`] for text, with the same non-ASCII check as
+	/// [`decode_str`]. In that case nothing is read nor written.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode_mut`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics in the same cases as [`decode_mut`].
+	///
+	/// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+	/// [`decode_str`]: struct.Encoding.html#method.decode_str
+	pub fn decode_str_mut(&self, input: &str, output: &mut [u8]) -> Result<usize, DecodePartial> {
+		if let Some(error) = self.non_ascii_error(input) {
+			return Err(DecodePartial {
+				read: 0,
+				written: 0,
+				error,
+			});
+		}
+		self.decode_mut(input.as_bytes(), output)
+	}
+
+	/// Returns the error for the first non-ASCII character of `input`, if it cannot be ignored
+	///
+	/// Symbols are ASCII, but non-ASCII bytes are skipped by encodings with ignored characters.
+	fn non_ascii_error(&self, input: &str) -> Option<DecodeError> {
+		if self.has_ignore() {
+			return None;
+		}
+		let position = input.bytes().position(|x| !x.is_ascii())?;
+		Some(DecodeError {
+			position,
+			kind: DecodeKind::Symbol,
+		})
+	}
+
+	/// Returns decoded `input`, rejecting symbols not in their canonical case
+	///
+	/// This is a diagnostic mode for case-insensitive encodings like [`BASE32_NOPAD_NOCASE`]:
+	/// `input` is decoded as usual, but a letter that is only accepted because its case is
+	/// translated is reported as an error.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid (see [`decode`]) or, with kind [`Symbol`], at the
+	/// first letter whose case differs from the canonical symbol, whichever comes first.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE32_NOPAD_NOCASE, DecodeKind};
+	/// assert_eq!(BASE32_NOPAD_NOCASE.decode_strict_case(b"MFRA").unwrap(), b"ab");
+	/// assert_eq!(BASE32_NOPAD_NOCASE.decode(b"mfra").unwrap(), b"ab");
+	/// let error = BASE32_NOPAD_NOCASE.decode_strict_case(b"MfRA").unwrap_err();
+	/// assert_eq!((error.position, error.kind), (1, DecodeKind::Symbol));
+	/// ```
+	///
+	/// [`BASE32_NOPAD_NOCASE`]: constant.BASE32_NOPAD_NOCASE.html
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	#[cfg(feature = "alloc")]
+	pub fn decode_strict_case(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		let (sym, val) = (self.sym(), self.val());
+		let case_error = input.iter().position(|&x| {
+			x.is_ascii_alphabetic() && {
+				let v = val[x as usize];
+				v < 128 && sym[v as usize] != x && sym[v as usize].eq_ignore_ascii_case(&x)
+			}
+		});
+		let result = self.decode(input);
+		match (case_error, &result) {
+			(Some(position), Err(error)) if error.position < position => result,
+			(Some(position), _) => Err(DecodeError {
+				position,
+				kind: DecodeKind::Symbol,
+			}),
+			(None, _) => result,
+		}
+	}
+
+	/// Returns decoded `input`, computed in parallel
+	///
+	/// The input is split at block boundaries in chunks of about 64 kilobytes, which are decoded
+	/// on the [rayon] thread pool. The output and errors are the same as [`decode`]: if a chunk
+	/// is invalid or padded, the whole input is decoded again sequentially to report the first
+	/// error. Arithmetic encodings and encodings ignoring characters (including wrapped ones)
+	/// cannot be split and decode sequentially.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode`] for more details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::HEXLOWER;
+	/// let input = "ca".repeat(1 << 20);
+	/// assert_eq!(HEXLOWER.par_decode(input.as_bytes()).unwrap(), vec![0xca; 1 << 20]);
+	/// ```
+	///
+	/// [rayon]: https://crates.io/crates/rayon
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "rayon")]
+	pub fn par_decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		use rayon::prelude::*;
+		if self.is_arithmetic() || self.has_ignore() {
+			return self.decode(input);
+		}
+		let bit = self.bit();
+		let blocks = PAR_CHUNK / dec(bit);
+		let (ichunk, ochunk) = (blocks * dec(bit) * 8 / bit, blocks * dec(bit));
+		// The last chunk is never empty, such that it holds the final padding.
+		let (head, tail) = input.split_at(input.len().saturating_sub(1) / ichunk * ichunk);
+		let start = head.len() / ichunk * ochunk;
+		let Ok(len) = self.decode_len(tail.len()) else {
+			return self.decode(input);
+		};
+		let mut output = vec![0u8; start + len];
+		let (left, right) = output.split_at_mut(start);
+		let valid =
+			head.par_chunks(ichunk).zip(left.par_chunks_mut(ochunk)).all(|(input, output)| {
+				let len = output.len();
+				matches!(self.decode_mut(input, output), Ok(written) if written == len)
+			});
+		match (valid, self.decode_mut(tail, right)) {
+			(true, Ok(written)) => {
+				output.truncate(start + written);
+				Ok(output)
+			}
+			_ => self.decode(input),
+		}
+	}
+
+	/// Returns decoded `input` in a buffer cleared on drop
+	///
+	/// This is like [`decode`] for secret data such as keys. The internal buffers and integer
+	/// accumulators of all functions, readers, and writers are also cleared when this feature is
+	/// enabled. Values in vector registers are not.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode`] for more details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let key = BASE64.decode_zeroizing(b"c2VjcmV0").unwrap();
+	/// assert_eq!(&key[..], b"secret");
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "zeroize")]
+	pub fn decode_zeroizing(
+		&self,
+		input: &[u8],
+	) -> Result<zeroize::Zeroizing<Vec<u8>>, DecodeError> {
+		let len = if self.is_arithmetic() {
+			self.arithmetic_decode_len(input)
+		} else {
+			self.decode_len(input.len())?
+		};
+		// Truncating keeps the capacity, which is cleared on drop as well.
+		let mut output = zeroize::Zeroizing::new(vec![0u8; len]);
+		let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
+		output.truncate(len);
+		Ok(output)
+	}
+
+	/// Returns decoded `input`, skipping an optional `0x` or `0X` prefix
+	///
+	/// This is meant for hexadecimal encodings, to parse literals like `0xDEADBEEF`. Error
+	/// positions are relative to `input`, prefix included.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` without its prefix is invalid. See [`decode`] for more
+	/// details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::HEXUPPER_PERMISSIVE;
+	/// assert_eq!(HEXUPPER_PERMISSIVE.decode_hex_prefixed(b"0xDEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+	/// assert_eq!(HEXUPPER_PERMISSIVE.decode_hex_prefixed(b"DEADBEEF").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "alloc")]
+	pub fn decode_hex_prefixed(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		let (prefix, input) = match input {
+			[b'0', b'x' | b'X', rest @ ..] => (2, rest),
+			_ => (0, input),
+		};
+		self.decode(input).map_err(|mut error| {
+			error.position += prefix;
+			error
+		})
+	}
+
+	/// Returns encoded `input` followed by its check symbol
+	///
+	/// This is meant for [`BASE32_CROCKFORD`]. The check symbol is the value of the encoded
+	/// symbols modulo 37, written with the symbols of the encoding followed by `*~$=U`.
+	///
+	/// # Panics
+	///
+	/// Panics if the encoding does not have 32 symbols.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE32_CROCKFORD;
+	/// assert_eq!(BASE32_CROCKFORD.encode_check_symbol(&[0x04, 0xd2]), "0K90Q");
+	/// ```
+	///
+	/// [`BASE32_CROCKFORD`]: constant.BASE32_CROCKFORD.html
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn encode_check_symbol(&self, input: &[u8]) -> String {
+		let mut output = self.encode(input);
+		let check = self.check_value(input);
+		output.push(match self.get_symbols().get(check) {
+			Some(&symbol) => symbol as char,
+			None => CHECK_SYMBOLS[check - 32] as char,
+		});
+		output
+	}
+
+	/// Returns decoded `input` after checking its last symbol
+	///
+	/// This is the inverse of [`encode_check_symbol`]. The check symbol is the last character
+	/// which is not ignored, and decodes case-insensitively like the other symbols.
+	///
+	/// # Panics
+	///
+	/// Panics if the encoding does not have 32 symbols.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` without its check symbol is invalid (see [`decode`]). The
+	/// error kind is [`Length`] if there is no check symbol, [`Symbol`] if it is invalid, and
+	/// [`Checksum`] if it does not match.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE32_CROCKFORD, DecodeKind};
+	/// assert_eq!(BASE32_CROCKFORD.decode_check_symbol(b"0k9-0q").unwrap(), [0x04, 0xd2]);
+	/// let error = BASE32_CROCKFORD.decode_check_symbol(b"0K90H").unwrap_err();
+	/// assert_eq!(error.kind, DecodeKind::Checksum);
+	/// ```
+	///
+	/// [`encode_check_symbol`]: struct.Encoding.html#method.encode_check_symbol
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	/// [`Checksum`]: enum.DecodeKind.html#variant.Checksum
+	#[cfg(feature = "alloc")]
+	pub fn decode_check_symbol(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		let val = self.val();
+		let ignored = |x: u8| val.get(x as usize) == Some(&IGNORE);
+		let position = input.iter().rposition(|&x| !ignored(x)).ok_or(DecodeError {
+			position: input.len(),
+			kind: DecodeKind::Length,
+		})?;
+		let output = self.decode(&input[..position])?;
+		let symbol = input[position];
+		let check = match val.get(symbol as usize) {
+			Some(&value) if usize::from(value) < self.get_symbols().len() => usize::from(value),
+			_ => {
+				let index = CHECK_SYMBOLS.iter().position(|&x| x == symbol.to_ascii_uppercase());
+				index.ok_or(DecodeError {
+					position,
+					kind: DecodeKind::Symbol,
+				})? + 32
+			}
+		};
+		check!(
+			DecodeError {
+				position,
+				kind: DecodeKind::Checksum
+			},
+			check == self.check_value(&output)
+		);
+		Ok(output)
+	}
+
+	/// Returns the value of the symbols encoding `input` modulo 37
+	///
+	/// The symbols encode `input` followed by zero bits up to a multiple of 5 bits.
+	#[cfg(feature = "alloc")]
+	fn check_value(&self, input: &[u8]) -> usize {
+		assert_eq!(self.get_symbols().len(), 32, "check symbols need 32 symbols");
+		let value = input.iter().fold(0, |value, &x| (value * 256 + usize::from(x)) % 37);
+		(value << ((5 - input.len() * 8 % 5) % 5)) % 37
+	}
+
+	/// Returns decoded bytes pulled from `iter`
+	///
+	/// The input is buffered one block at a time, so it never needs to be collected into a slice.
+	/// Error positions count the bytes pulled from `iter` before the invalid one. Arithmetic
+	/// encodings have no blocks, so their input is collected before decoding.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the input is invalid. See [`decode`] for more details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let input = "SGVsbA==byB3b3JsZA==".bytes();
+	/// assert_eq!(BASE64.decode_from_iter(input).unwrap(), b"Hello world");
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "alloc")]
+	pub fn decode_from_iter(&self, iter: impl Iterator<Item = u8>) -> Result<Vec<u8>, DecodeError> {
+		let mut output = Vec::new();
+		let mut decoder = self.new_decoder(&mut output);
+		for byte in iter {
+			decoder.push(byte)?;
+		}
+		decoder.finalize()?;
+		Ok(output)
+	}
+
+	/// Appends decoded `block` to `output`, shifting error positions by `start`
+	#[cfg(feature = "alloc")]
+	fn decode_block_append(
+		&self,
+		block: &[u8],
+		start: usize,
+		output: &mut Vec<u8>,
+	) -> Result<(), DecodeError> {
+		let shift = |mut error: DecodeError| {
+			error.position += start;
+			error
+		};
+		let len = output.len();
+		output.resize(len + self.decode_len(block.len()).map_err(shift)?, 0);
+		let written =
+			self.decode_mut(block, &mut output[len..]).map_err(|partial| shift(partial.error))?;
+		output.truncate(len + written);
+		Ok(())
+	}
+
+	/// Returns a reader decoding the content of `reader`
+	///
+	/// All ASCII whitespace is skipped, regardless of the characters this encoding ignores, so line
+	/// breaks may appear anywhere in the input. Decoding errors are returned as
+	/// [`InvalidData`] errors wrapping a [`DecodeError`] whose position does not count
+	/// whitespace.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// use std::io::Read;
+	/// let mut output = Vec::new();
+	/// BASE64.decode_read_lenient(&b"SGVs\r\nbG8g d29y\nbGQ="[..]).read_to_end(&mut output).unwrap();
+	/// assert_eq!(output, b"Hello world");
+	/// ```
+	///
+	/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+	#[cfg(feature = "std")]
+	pub fn decode_read_lenient<R: std::io::Read>(&self, reader: R) -> DecodeReader<'_, R> {
+		DecodeReader {
+			encoding: self,
+			reader,
+			lenient: true,
+			pending: Vec::new(),
+			decoded: Vec::new(),
+			position: 0,
+			consumed: 0,
+			eof: false,
+		}
+	}
+
+	/// Returns a writer encoding to `writer`
+	///
+	/// Complete blocks are encoded as they are written. The last partial block is encoded by
+	/// [`EncoderWriter::finish`], or when the writer is dropped (ignoring errors). Arithmetic
+	/// encodings have no blocks, so their input is kept until then.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// use std::io::Write;
+	/// let mut writer = BASE64.encode_writer(Vec::new());
+	/// writer.write_all(b"Hello ").unwrap();
+	/// writer.write_all(b"world").unwrap();
+	/// assert_eq!(writer.finish().unwrap(), b"SGVsbG8gd29ybGQ=");
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn encode_writer<W: std::io::Write>(&self, writer: W) -> EncoderWriter<'_, W> {
+		EncoderWriter {
+			encoding: self,
+			writer: Some(writer),
+			pending: Vec::new(),
+		}
+	}
+
+	/// Returns a reader decoding the content of `reader`
+	///
+	/// This is like [`decode_read_lenient`] but the input is decoded as by [`decode`], without
+	/// skipping whitespace. Decoding errors are returned as [`InvalidData`] errors wrapping a
+	/// [`DecodeError`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// use std::io::Read;
+	/// let mut output = Vec::new();
+	/// BASE64.decode_reader(&b"SGVsbG8gd29ybGQ="[..]).read_to_end(&mut output).unwrap();
+	/// assert_eq!(output, b"Hello world");
+	/// ```
+	///
+	/// [`decode_read_lenient`]: Encoding::decode_read_lenient
+	/// [`decode`]: Encoding::decode
+	/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+	#[cfg(feature = "std")]
+	pub fn decode_reader<R: std::io::Read>(&self, reader: R) -> DecodeReader<'_, R> {
+		DecodeReader {
+			encoding: self,
+			reader,
+			lenient: false,
+			pending: Vec::new(),
+			decoded: Vec::new(),
+			position: 0,
+			consumed: 0,
+			eof: false,
+		}
+	}
+
+	/// Returns `unpadded` with the padding of this encoding appended
+	///
+	/// This is equivalent to decoding with the unpadded variant of this encoding and encoding the
+	/// result again, without the round-trip. Encodings without padding return the input unchanged.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `unpadded` is not a valid unpadded encoding. The error kind can be:
+	///
+	/// - [`Symbol`] if the input contains a character that is not a symbol, including padding
+	///   characters. The [position] is the first such character.
+	/// - [`Length`] if no amount of padding would make the input valid. The [position] is the
+	///   greatest valid input length.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// assert_eq!(BASE64.add_padding("QQ").unwrap(), "QQ==");
+	/// assert_eq!(BASE64.add_padding("SGVsbG8").unwrap(), "SGVsbG8=");
+	/// assert!(BASE64.add_padding("Q").is_err());
+	/// ```
+	///
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	/// [position]: struct.DecodeError.html#structfield.position
+	#[cfg(feature = "alloc")]
+	pub fn add_padding(&self, unpadded: &str) -> Result<String, DecodeError> {
+		let input = unpadded.as_bytes();
+		let symbols = self.get_symbols().len();
+		if let Some(position) =
+			input.iter().position(|&x| x >= 128 || self.val()[x as usize] as usize >= symbols)
+		{
+			return Err(DecodeError {
+				position,
+				kind: DecodeKind::Symbol,
+			});
+		}
+		let pad = match self.pad() {
+			Some(pad) if !self.is_arithmetic() => pad,
+			_ => return Ok(unpadded.to_owned()),
+		};
+		let bit = self.bit();
+		let group = self.pad_group();
+		let valid = |len: usize| (len % group * bit) % 8 < bit;
+		if !valid(input.len()) {
+			let position = (0..input.len()).rev().find(|&len| valid(len)).unwrap_or(0);
+			return Err(DecodeError {
+				position,
+				kind: DecodeKind::Length,
+			});
+		}
+		let mut output = unpadded.to_owned();
+		let rem = input.len() % group;
+		if rem != 0 {
+			output.extend(core::iter::repeat_n(pad as char, group - rem));
+		}
+		Ok(output)
+	}
+
+	/// Returns `padded` with the padding of this encoding removed
+	///
+	/// This is the inverse of [`add_padding`]. Encodings without padding validate the input and
+	/// return it unchanged.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `padded` is not validly padded. The error kind can be:
+	///
+	/// - [`Padding`] if a padding character is followed by a symbol, or if the number of padding
+	///   characters is wrong. The [position] is the first offending padding character.
+	/// - [`Symbol`] and [`Length`] as for [`add_padding`], on the input without padding.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// assert_eq!(BASE64.strip_padding("QQ==").unwrap(), "QQ");
+	/// assert_eq!(BASE64.strip_padding("SGVsbG8=").unwrap(), "SGVsbG8");
+	/// assert!(BASE64.strip_padding("Q===").is_err());
+	/// ```
+	///
+	/// [`add_padding`]: struct.Encoding.html#method.add_padding
+	/// [`Padding`]: enum.DecodeKind.html#variant.Padding
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	/// [position]: struct.DecodeError.html#structfield.position
+	#[cfg(feature = "alloc")]
+	pub fn strip_padding(&self, padded: &str) -> Result<String, DecodeError> {
+		let unpadded = match self.pad() {
+			Some(pad) if !self.is_arithmetic() => {
+				let unpadded = padded.trim_end_matches(pad as char);
+				if let Some(position) = unpadded.bytes().position(|x| x == pad) {
+					return Err(DecodeError {
+						position,
+						kind: DecodeKind::Padding,
+					});
+				}
+				unpadded
+			}
+			_ => padded,
+		};
+		if self.add_padding(unpadded)?.len() != padded.len() {
+			return Err(DecodeError {
+				position: unpadded.len(),
+				kind: DecodeKind::Padding,
+			});
+		}
+		Ok(unpadded.to_owned())
+	}
+
+	/// Returns decoded `input` if it is the canonical encoding of its output
+	///
+	/// This is stricter than [`decode`]: the input must be exactly what [`encode`] produces for the
+	/// decoded bytes, even when the encoding itself is not canonical (see [`is_canonical`]).
+	///
+	/// # Errors
+	///
+	/// Returns the errors of [`decode`], and otherwise an error at the first character differing
+	/// from the canonical encoding. The error kind can be:
+	///
+	/// - [`Trailing`] if the character has the wrong value, which means non-zero trailing bits.
+	/// - [`Padding`] if the character is a padding character.
+	/// - [`Length`] if the input is a prefix of the canonical encoding or the other way around.
+	/// - [`Symbol`] otherwise, for example a translated or ignored character.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64, HEXLOWER_PERMISSIVE};
+	/// assert_eq!(BASE64.decode_canonical(b"QQ==").unwrap(), b"A");
+	/// assert!(BASE64.decode_canonical(b"QR==").is_err());
+	/// assert!(HEXLOWER_PERMISSIVE.decode_canonical(b"2A").is_err());
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`encode`]: struct.Encoding.html#method.encode
+	/// [`is_canonical`]: struct.Encoding.html#method.is_canonical
+	/// [`Trailing`]: enum.DecodeKind.html#variant.Trailing
+	/// [`Padding`]: enum.DecodeKind.html#variant.Padding
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	/// [`Symbol`]: enum.DecodeKind.html#variant.Symbol
+	#[cfg(feature = "alloc")]
+	pub fn decode_canonical(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		let output = self.decode(input)?;
+		let canonical = self.encode(&output);
+		let canonical = canonical.as_bytes();
+		let Some(position) = input.iter().zip(canonical).position(|(x, y)| x != y) else {
+			check!(
+				DecodeError {
+					position: core::cmp::min(input.len(), canonical.len()),
+					kind: DecodeKind::Length,
+				},
+				input.len() == canonical.len()
+			);
+			return Ok(output);
+		};
+		let x = input[position];
+		let value = |x: u8| {
+			if x < 128 {
+				self.val()[x as usize]
+			} else {
+				INVALID
+			}
+		};
+		let v = value(x);
+		let kind = if Some(x) == self.pad() {
+			DecodeKind::Padding
+		} else if (v as usize) < self.get_symbols().len() && v != value(canonical[position]) {
+			DecodeKind::Trailing
+		} else {
+			DecodeKind::Symbol
+		};
+		Err(DecodeError {
+			position,
+			kind,
+		})
+	}
+
+	/// Returns the decoded segments of concatenated padded `input`
+	///
+	/// A segment ends after each run of padding characters, and at the end of the input. Each
+	/// segment is decoded separately. Segments made only of ignored characters are skipped.
+	/// Encodings without padding always return at most one segment.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a segment is invalid. See [`decode`] for more details. The [position] is
+	/// relative to the whole input.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// let segments = BASE64.decode_segments(b"SGVsbA==byB3b3JsZA==").unwrap();
+	/// assert_eq!(segments, [&b"Hell"[..], b"o world"]);
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [position]: struct.DecodeError.html#structfield.position
+	#[cfg(feature = "alloc")]
+	pub fn decode_segments(&self, input: &[u8]) -> Result<Vec<Vec<u8>>, DecodeError> {
+		let is_pad = |x: u8| x < 128 && self.val()[x as usize] == PADDING;
+		let mut segments = Vec::new();
+		let mut start = 0;
+		for end in 1..=input.len() {
+			let boundary = end == input.len() || (is_pad(input[end - 1]) && !is_pad(input[end]));
+			if !boundary {
+				continue;
+			}
+			let segment = self.decode(&input[start..end]).map_err(|mut error| {
+				error.position += start;
+				error
+			})?;
+			if !segment.is_empty() {
+				segments.push(segment);
+			}
+			start = end;
+		}
+		Ok(segments)
+	}
+
+	/// Returns decoded `input` left-padded with zeros to exactly `width` bytes
+	///
+	/// This is meant for arithmetic encodings like [`BASE58`], which encode big-endian integers and
+	/// only keep the leading zeros that are explicitly encoded. Leading zeros beyond `width` are
+	/// dropped.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid. See [`decode`] for more details. Returns an error of
+	/// kind [`Overflow`] at position 0 if the decoded value does not fit in `width` bytes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE58;
+	/// assert_eq!(BASE58.decode_fixed(b"5R", 4).unwrap(), [0, 0, 1, 0]);
+	/// assert!(BASE58.decode_fixed(b"5R", 1).is_err());
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	/// [`Overflow`]: enum.DecodeKind.html#variant.Overflow
+	#[cfg(feature = "alloc")]
+	pub fn decode_fixed(&self, input: &[u8], width: usize) -> Result<Vec<u8>, DecodeError> {
+		let output = self.decode(input)?;
+		let zeros = output.iter().take_while(|&&x| x == 0).count();
+		let value = &output[zeros..];
+		check!(
+			DecodeError {
+				position: 0,
+				kind: DecodeKind::Overflow,
+			},
+			value.len() <= width
+		);
+		let mut fixed = vec![0u8; width];
+		fixed[width - value.len()..].copy_from_slice(value);
+		Ok(fixed)
+	}
+
+	/// Returns decoded `input` along with all decoding errors
+	///
+	/// Unlike [`decode`], this does not stop at the first error. The input is decoded group by
+	/// group (4 symbols for base64, 8 for base32, 2 for hexadecimal), ignoring ignored characters.
+	/// Invalid groups are skipped and produce no output. Every invalid symbol of a group is
+	/// reported, otherwise the first error of the group is.
+	///
+	/// Arithmetic encodings are decoded as a whole and report at most one error.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64, DecodeKind};
+	/// let (output, errors) = BASE64.decode_report(b"QUJD.UJDQU*DQQ==");
+	/// assert_eq!(output, b"ABCA");
+	/// assert_eq!(errors.len(), 2);
+	/// assert_eq!((errors[0].position, errors[0].kind), (4, DecodeKind::Symbol));
+	/// assert_eq!((errors[1].position, errors[1].kind), (10, DecodeKind::Symbol));
+	/// ```
+	///
+	/// [`decode`]: struct.Encoding.html#method.decode
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn decode_report(&self, input: &[u8]) -> (Vec<u8>, Vec<DecodeError>) {
+		if self.is_arithmetic() {
+			return match self.decode(input) {
+				Ok(output) => (output, Vec::new()),
+				Err(error) => (Vec::new(), vec![error]),
+			};
+		}
+		let val = self.val();
+		let num_symbols = self.get_symbols().len();
+		let value = |x: u8| {
+			if x < 128 {
+				val[x as usize]
+			} else {
+				INVALID
+			}
+		};
+		let positions: Vec<usize> =
+			(0..input.len()).filter(|&i| value(input[i]) != IGNORE).collect();
+		let mut output = Vec::new();
+		let mut errors = Vec::new();
+		let mut group = Vec::new();
+		for chunk in positions.chunks(self.pad_group()) {
+			group.clear();
+			group.extend(chunk.iter().map(|&i| input[i]));
+			match self.decode(&group) {
+				Ok(decoded) => output.extend_from_slice(&decoded),
+				Err(error) if error.kind == DecodeKind::Symbol => {
+					for (&position, &x) in chunk.iter().zip(&group) {
+						let v = value(x);
+						if (v as usize) < num_symbols || v == PADDING {
+							continue;
+						}
+						errors.push(DecodeError {
+							position,
+							kind: DecodeKind::Symbol,
+						});
+					}
+				}
+				Err(error) => errors.push(DecodeError {
+					position: chunk.get(error.position).map_or(input.len(), |&i| i),
+					kind: error.kind,
+				}),
+			}
+		}
+		(output, errors)
+	}
+
+	/// Returns the bit-width
+	#[must_use]
+	pub fn bit_width(&self) -> usize {
+		if self.is_arithmetic() {
+			let base = self.data()[512] as u32;
+			if base == 0 {
+				return 0;
+			}
+			(32 - base.leading_zeros()) as usize
+		} else {
+			self.bit()
+		}
+	}
+
+	/// Returns the number of bits of entropy per symbol
+	///
+	/// This is `log2` of the number of symbols: exactly 6 for base64, and about 5.858 for base58.
+	/// It helps sizing identifiers for a target entropy.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58, BASE64};
+	/// assert_eq!(BASE64.bits_per_symbol(), 6.0);
+	/// assert!((BASE58.bits_per_symbol() - 5.858).abs() < 1e-3);
+	/// ```
+	#[cfg(feature = "std")]
+	#[must_use]
+	pub fn bits_per_symbol(&self) -> f64 {
+		#[allow(clippy::cast_precision_loss)] // at most 256 symbols
+		let len = self.get_symbols().len() as f64;
+		len.log2()
+	}
+
+	/// Returns whether encoding then decoding an input of length `input_len` recovers exactly
+	/// `input_len` bytes
+	///
+	/// Encoding never loses data: padded and arithmetic encodings are exact, and the last symbol of
+	/// unpadded encodings has fewer than 8 trailing bits, which never make up a whole byte. So this
+	/// only returns false if the encoded length overflows, like [`encode_len`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE32_NOPAD, HEXLOWER};
+	/// assert!(BASE32_NOPAD.roundtrips_len(4));
+	/// assert!(!HEXLOWER.roundtrips_len(usize::MAX));
+	/// ```
+	///
+	/// [`encode_len`]: struct.Encoding.html#method.encode_len
+	#[must_use]
+	pub fn roundtrips_len(&self, input_len: usize) -> bool {
+		self.encode_len(input_len).is_ok()
+	}
+
+	/// Returns whether `byte` may appear in an encoded input
+	///
+	/// This is true for symbols (including translated characters), the padding character, and
+	/// ignored characters. It lets a tokenizer extend an encoded run one byte at a time.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::BASE64;
+	/// assert!(BASE64.accepts(b'A'));
+	/// assert!(BASE64.accepts(b'='));
+	/// assert!(!BASE64.accepts(b'!'));
+	/// ```
+	#[must_use]
+	pub fn accepts(&self, byte: u8) -> bool {
+		if self.is_arithmetic() {
+			let zero = self.arithmetic_block().and_then(|blocks| blocks.zero);
+			return self.get_symbols().contains(&byte) || zero == Some(byte);
+		}
+		byte < 128 && self.val()[byte as usize] != INVALID
+	}
+
+	/// Returns the wrap width, if the output is wrapped
+	///
+	/// This is the maximum number of symbols per line, not counting the separator.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64, BASE64_MIME};
+	/// assert_eq!(BASE64_MIME.wrap_width(), Some(76));
+	/// assert_eq!(BASE64.wrap_width(), None);
+	/// ```
+	#[must_use]
+	pub fn wrap_width(&self) -> Option<usize> {
+		self.wrap().map(|(col, _)| col)
+	}
+
+	/// Returns the wrap separator, if the output is wrapped
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64, BASE64_MIME};
+	/// assert_eq!(BASE64_MIME.wrap_separator(), Some(&b"\r\n"[..]));
+	/// assert_eq!(BASE64.wrap_separator(), None);
+	/// ```
+	#[must_use]
+	pub fn wrap_separator(&self) -> Option<&[u8]> {
+		self.wrap().map(|(_, end)| end)
+	}
+
+	/// Returns whether the encoding is canonical
+	///
+	/// An encoding is canonical if:
+	///
+	/// - trailing bits are zero
+	/// - no characters are ignored
+	/// - no characters are translated
+	/// - for padded encodings, there is no trailing padding
+	///
+	/// In other words, an encoding is canonical if all inputs that could be produced by the
+	/// encoding function are successfully decoded, and all other inputs are rejected.
+	///
+	/// Canonical encodings are useful for hashing.
+	///
+	/// A non-canonical encoding can be made canonical if:
+	///
+	/// - trailing bits are checked
+	/// - padding is used
+	/// - characters are ignored
+	/// - characters are translated
+	#[must_use]
+	pub fn is_canonical(&self) -> bool {
+		if !self.ctb() {
+			return false;
+		}
+		let symbols = self.get_symbols();
+		let num_symbols = symbols.len();
+		if num_symbols == 0 {
+			return false;
+		}
+		let sym = self.sym();
+		let val = self.val();
+		for i in 0..256 {
+			if i >= val.len() || val[i] == INVALID {
+				continue;
+			}
+			if (val[i] as usize) >= num_symbols {
+				return false;
+			}
+			if sym[val[i] as usize] as usize != i {
+				return false;
+			}
+		}
+		true
+	}
+
+	/// Returns a one-line human-readable description
+	///
+	/// This is meant for debugging and command-line tools, unlike [`Debug`] which shows the raw
+	/// implementation. The format is not stable.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE64_MIME, BASE64_NOPAD};
+	/// assert_eq!(BASE64_NOPAD.describe(), "base64 (MSB, unpadded, 6 bits/symbol, canonical)");
+	/// assert_eq!(
+	///     BASE64_MIME.describe(),
+	///     "base64 (MSB, padded '=', standard, 6 bits/symbol, wrapped at 76, non-canonical)",
+	/// );
+	/// ```
+	///
+	/// [`Debug`]: core::fmt::Debug
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn describe(&self) -> String {
+		use core::fmt::Write as _;
+
+		let mut output = String::new();
+		let _ = write!(output, "base{} (", self.get_symbols().len());
+		if let Some(blocks) = self.arithmetic_block() {
+			let _ = write!(output, "arithmetic, {}-byte blocks", blocks.size);
+		} else if self.is_arithmetic() {
+			output.push_str("arithmetic");
+		} else {
+			output.push_str(if self.msb() { "MSB" } else { "LSB" });
+		}
+		match self.pad() {
+			Some(pad) => {
+				let mode = match self.pad_mode() {
+					PaddingMode::PadConcat => "concat",
+					PaddingMode::PadFinal => "final",
+					_ => "standard",
+				};
+				let _ = write!(output, ", padded '{}', {mode}", pad as char);
+			}
+			None => output.push_str(", unpadded"),
+		}
+		if !self.is_arithmetic() {
+			let _ = write!(output, ", {} bits/symbol", self.bit());
+		}
+		if let Some((col, _)) = self.wrap() {
+			let _ = write!(output, ", wrapped at {col}");
+		}
+		if self.constant_time() {
+			output.push_str(", constant-time");
+		}
+		output.push_str(if self.is_canonical() { ", canonical)" } else { ", non-canonical)" });
+		output
+	}
+
+	/// Returns the encoding specification
+	#[allow(clippy::missing_panics_doc)] // no panic
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn specification(&self) -> Specification {
+		let mut specification = Specification::new();
 		let symbols = self.get_symbols();
 		specification.symbols.push_str(core::str::from_utf8(symbols).unwrap_or(""));
 		specification.bit_order = if self.msb() {
@@ -2010,109 +4320,735 @@ impl Encoding {
 			if i == canonical {
 				continue;
 			}
-			specification.translate.from.push(i as char);
-			specification.translate.to.push(canonical as char);
+			specification.translate.from.push(i as char);
+			specification.translate.to.push(canonical as char);
+		}
+		specification.use_arithmetic = self.is_arithmetic();
+		specification.allow_trailing_pad_group = self.allow_trailing_pad_group();
+		specification.constant_time = self.constant_time();
+		if let Some(blocks) = self.arithmetic_block() {
+			specification.arithmetic_block_size = blocks.size;
+			specification.arithmetic_pad_last_block = blocks.pad_last;
+			specification.arithmetic_zero_block = blocks.zero.map(char::from);
+		}
+		specification
+	}
+
+	/// Returns the encoding of a specification, at compile time in `const` context
+	///
+	/// This lets custom encodings be constants, like the predefined ones. See
+	/// [`ConstSpecification`] for an example.
+	///
+	/// # Panics
+	///
+	/// Panics if the specification is invalid, which fails compilation in `const` context. Use
+	/// [`ConstSpecification::encoding`] to handle the error instead.
+	#[must_use]
+	pub const fn from_spec_const(specification: &ConstSpecification<'_>) -> Encoding {
+		match specification.encoding() {
+			Ok(encoding) => encoding,
+			Err(_) => panic!("invalid specification"),
+		}
+	}
+
+	#[doc(hidden)]
+	#[must_use]
+	pub const fn internal_new(implementation: &'static [u8]) -> Encoding {
+		// The symbol and value tables are read without bounds checks.
+		assert!(implementation.len() >= MIN_IMPLEMENTATION_LEN, "implementation too short");
+		Encoding(InternalEncoding::Static(implementation))
+	}
+
+	#[doc(hidden)]
+	#[must_use]
+	pub fn internal_implementation(&self) -> &[u8] {
+		&*self.0
+	}
+}
+
+/// Decodes `input` with `from` and appends its encoding with `to` to `out`
+///
+/// The decoded bytes go through a single scratch buffer. Nothing is appended to `out` if
+/// decoding fails.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid for `from`. See [`Encoding::decode`] for the error kinds.
+///
+/// # Panics
+///
+/// Panics if the encoding with `to` fails (e.g. length overflow).
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::{BASE32, BASE64, transcode};
+/// let mut out = Vec::new();
+/// transcode(&BASE32, &BASE64, b"MZXW6YTBOI======", &mut out).unwrap();
+/// assert_eq!(out, b"Zm9vYmFy");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn transcode(
+	from: &Encoding,
+	to: &Encoding,
+	input: &[u8],
+	out: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+	let mut scratch = vec![0u8; from.decode_len(input.len())?];
+	let len = from.decode_mut(input, &mut scratch).map_err(|partial| partial.error)?;
+	let decoded = &scratch[..len];
+	let start = out.len();
+	out.resize(start + to.encode_len(decoded.len()).expect("encoding length overflow"), 0);
+	let written = to.encode_mut(decoded, &mut out[start..]).expect("encoding failed");
+	out.truncate(start + written);
+	Ok(())
+}
+
+/// Encodes fragmented input to an output
+///
+/// Use this struct if your input is in several pieces. The output is the same as encoding the
+/// whole input at once, including the line breaks of wrapped encodings: the column of the
+/// current line is kept across calls to [`append`].
+///
+/// [`append`]: Encoder::append
+#[derive(Debug)]
+pub struct Encoder<'a> {
+	encoding: &'a Encoding,
+	output: &'a mut String,
+	buffer: [u8; 8],
+	length: usize,
+	/// Number of symbols on the current line, when wrapping.
+	column: usize,
+}
+
+impl Drop for Encoder<'_> {
+	fn drop(&mut self) {
+		self.finalize();
+		// The buffer held input bytes, like a Scratch.
+		#[cfg(feature = "zeroize")]
+		zeroize::Zeroize::zeroize(&mut self.buffer);
+	}
+}
+
+impl<'a> Encoder<'a> {
+	fn new(encoding: &'a Encoding, output: &'a mut String) -> Encoder<'a> {
+		Encoder {
+			encoding,
+			output,
+			buffer: [0u8; 8],
+			length: 0,
+			column: 0,
+		}
+	}
+
+	/// Appends the encoding of `input` to the output
+	pub fn append(&mut self, input: &[u8]) {
+		if input.is_empty() {
+			return;
+		}
+		let mut input = input;
+		// Line breaks are inserted by write, so a block never covers whole lines.
+		let ilen = dec(self.encoding.bit());
+		if self.length > 0 {
+			let n = core::cmp::min(ilen - self.length, input.len());
+			self.buffer[self.length..self.length + n].copy_from_slice(&input[0..n]);
+			self.length += n;
+			input = &input[n..];
+			if self.length == ilen {
+				let buffer = &self.buffer[0..ilen];
+				Encoder::write(self.encoding, self.output, &mut self.column, buffer);
+				self.length = 0;
+			}
+		}
+		let n = floor(input.len(), ilen);
+		Encoder::write(self.encoding, self.output, &mut self.column, &input[0..n]);
+		input = &input[n..];
+		if !input.is_empty() {
+			self.buffer[0..input.len()].copy_from_slice(input);
+			self.length = input.len();
+		}
+	}
+
+	/// Appends the encoding of `input` with line breaks from the current column
+	///
+	/// This takes the fields separately, such that `input` may borrow the buffer without copying
+	/// it (it may hold secret data).
+	fn write(encoding: &Encoding, output: &mut String, column: &mut usize, input: &[u8]) {
+		let Some((col, end)) = encoding.wrap() else {
+			encoding.encode_append(input, output);
+			return;
+		};
+		let bit = encoding.bit();
+		let msb = encoding.msb();
+		let pad_mode = encoding.pad_mode();
+		let (sym, pad) = (encoding.sym(), encoding.pad());
+		let mut symbols = [0u8; 1024];
+		for input in input.chunks(symbols.len() / (dec(bit) * 8 / bit) * dec(bit)) {
+			let len = dispatch! {
+				let bit: usize = bit;
+				let pad_mode: PaddingMode = pad_mode;
+				encode_wrap_len(bit, pad_mode, None, input.len())
+			}
+			.expect("encoding length overflow");
+			let buffer = &mut symbols[..len];
+			let written = dispatch! {
+				let bit: usize = bit;
+				let msb: bool = msb;
+				let pad_mode: PaddingMode = pad_mode;
+				encode_wrap_mut(bit, msb, pad_mode, sym, pad, None, input, buffer)
+			};
+			// SAFETY: Symbols are ASCII and the separator comes from a string.
+			let output = unsafe { output.as_mut_vec() };
+			let mut symbols = &symbols[..written];
+			while !symbols.is_empty() {
+				let n = core::cmp::min(col - *column, symbols.len());
+				output.extend_from_slice(&symbols[..n]);
+				symbols = &symbols[n..];
+				*column += n;
+				if *column == col {
+					output.extend_from_slice(end);
+					*column = 0;
+				}
+			}
+		}
+		// The symbols encode the input, like a Scratch.
+		#[cfg(feature = "zeroize")]
+		zeroize::Zeroize::zeroize(&mut symbols);
+	}
+
+	/// Finalizes the encoding
+	pub fn finalize(&mut self) {
+		if self.length > 0 {
+			let buffer = &self.buffer[0..self.length];
+			Encoder::write(self.encoding, self.output, &mut self.column, buffer);
+			self.length = 0;
+		}
+		// The last line is terminated like the other ones.
+		if let (Some((_, end)), 1..) = (self.encoding.wrap(), self.column) {
+			// SAFETY: The separator comes from a string.
+			unsafe { self.output.as_mut_vec() }.extend_from_slice(end);
+			self.column = 0;
+		}
+	}
+}
+
+/// Decodes fragmented input to an output
+///
+/// Use this struct if your input arrives in several pieces, like chunks read from a socket.
+/// Complete blocks are decoded as soon as they are appended, while a partial block is kept until
+/// more input completes it or [`finalize`] checks it (padding and trailing bits). Error
+/// positions count all the input appended so far. Arithmetic encodings have no blocks, so their
+/// input is kept until [`finalize`].
+///
+/// The output is unspecified after an error.
+///
+/// [`finalize`]: Decoder::finalize
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Decoder<'a> {
+	encoding: &'a Encoding,
+	output: &'a mut Vec<u8>,
+	state: DecodeState,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Decoder<'a> {
+	fn new(encoding: &'a Encoding, output: &'a mut Vec<u8>) -> Decoder<'a> {
+		Decoder {
+			encoding,
+			output,
+			state: DecodeState::default(),
+		}
+	}
+
+	fn push(&mut self, byte: u8) -> Result<(), DecodeError> {
+		self.state.push(self.encoding, byte, self.output)
+	}
+
+	/// Appends the decoding of `input` to the output
+	///
+	/// # Errors
+	///
+	/// Returns an error if a complete block is invalid. See [`Encoding::decode`] for more details.
+	pub fn append(&mut self, input: &[u8]) -> Result<(), DecodeError> {
+		input.iter().try_for_each(|&byte| self.push(byte))
+	}
+
+	/// Finalizes the decoding
+	///
+	/// # Errors
+	///
+	/// Returns an error if the last block is invalid, for example if it is truncated, its
+	/// padding is wrong, or its trailing bits are not zero. See [`Encoding::decode`] for more
+	/// details.
+	pub fn finalize(self) -> Result<(), DecodeError> {
+		self.state.finalize(self.encoding, self.output)
+	}
+}
+
+/// Partial block of a [`Decoder`]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+struct DecodeState {
+	/// Input of the current block, including ignored characters.
+	block: Vec<u8>,
+	/// Number of symbols in the current block.
+	symbols: usize,
+	/// Number of bytes pushed before the current block.
+	start: usize,
+}
+
+// The pending input may be secret, like a Scratch.
+#[cfg(feature = "zeroize")]
+impl Drop for DecodeState {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.block);
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl DecodeState {
+	fn push(
+		&mut self,
+		encoding: &Encoding,
+		byte: u8,
+		output: &mut Vec<u8>,
+	) -> Result<(), DecodeError> {
+		self.block.push(byte);
+		if encoding.is_arithmetic() || byte < 128 && encoding.val()[byte as usize] == IGNORE {
+			return Ok(());
+		}
+		self.symbols += 1;
+		if self.symbols == encoding.pad_group() {
+			encoding.decode_block_append(&self.block, self.start, output)?;
+			self.start += self.block.len();
+			self.block.clear();
+			self.symbols = 0;
+		}
+		Ok(())
+	}
+
+	fn finalize(self, encoding: &Encoding, output: &mut Vec<u8>) -> Result<(), DecodeError> {
+		if encoding.is_arithmetic() {
+			output.extend(encoding.decode(&self.block)?);
+			return Ok(());
+		}
+		encoding.decode_block_append(&self.block, self.start, output)
+	}
+}
+
+/// Arithmetic encoding with its decoding table built once
+///
+/// See [`Encoding::prepare_arithmetic`].
+#[derive(Debug, Clone)]
+pub struct PreparedArithmetic<'a> {
+	encoding: &'a Encoding,
+	lookup: [u8; 256],
+}
+
+impl<'a> PreparedArithmetic<'a> {
+	/// Returns the prepared encoding
+	#[must_use]
+	pub fn encoding(&self) -> &'a Encoding {
+		self.encoding
+	}
+
+	/// Encodes `input` in `output`
+	///
+	/// Encoding does not use the table, so this is the same as [`Encoding::encode_mut`].
+	///
+	/// # Errors
+	///
+	/// See [`Encoding::encode_mut`].
+	pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, EncodeError> {
+		self.encoding.encode_mut(input, output)
+	}
+
+	/// Returns encoded `input`
+	///
+	/// This is the same as [`Encoding::encode`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn encode(&self, input: &[u8]) -> String {
+		self.encoding.encode(input)
+	}
+
+	/// Decodes `input` in `output`
+	///
+	/// This is the same as [`Encoding::decode_mut`] without building the table, except that
+	/// input is not checked to be UTF-8 first: a non-ASCII byte is an invalid symbol.
+	///
+	/// # Errors
+	///
+	/// See [`Encoding::decode_mut`].
+	pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
+		let symbols = self.encoding.get_symbols();
+		let lookup = &self.lookup;
+		let result = match self.encoding.arithmetic_block() {
+			Some(blocks) => arithmetic::decode_blocks(symbols, lookup, blocks, input, output),
+			None => arithmetic::decode_with_lookup::<{ arithmetic::MAX_BIGINT_BUFFER }>(
+				symbols, lookup, input, output, true,
+			),
+		};
+		result.map_err(|error| DecodePartial {
+			read: error.position,
+			written: 0,
+			error,
+		})
+	}
+
+	/// Returns decoded `input`
+	///
+	/// This is the same as [`Encoding::decode`] without building the table, except that input
+	/// is not checked to be UTF-8 first: a non-ASCII byte is an invalid symbol.
+	///
+	/// # Errors
+	///
+	/// See [`Encoding::decode`].
+	#[cfg(feature = "alloc")]
+	pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+		let mut output = vec![0u8; self.encoding.arithmetic_decode_len(input)];
+		let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
+		output.truncate(len);
+		Ok(output)
+	}
+}
+
+/// Encoding with a version prefix and a checksum, like Base58Check
+///
+/// The encoded data is a version byte, the payload, and the first 4 bytes of the double SHA-256
+/// of both. See [`Encoding::checked`] and [`BASE58CHECK`].
+#[derive(Debug, Clone, Copy)]
+pub struct CheckedEncoding<'a> {
+	encoding: &'a Encoding,
+}
+
+impl<'a> CheckedEncoding<'a> {
+	/// Returns the underlying encoding
+	#[must_use]
+	pub fn encoding(&self) -> &'a Encoding {
+		self.encoding
+	}
+
+	/// Returns encoded `payload` with its `version` and checksum
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58CHECK, HEXLOWER};
+	/// let hash = HEXLOWER.decode(b"62e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap();
+	/// assert_eq!(BASE58CHECK.encode(0, &hash), "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn encode(&self, version: u8, payload: &[u8]) -> String {
+		let mut data = Vec::with_capacity(payload.len() + 5);
+		data.push(version);
+		data.extend_from_slice(payload);
+		let checksum = sha256::checksum(&data);
+		data.extend_from_slice(&checksum);
+		self.encoding.encode(&data)
+	}
+
+	/// Returns the version and payload of `input` after checking its checksum
+	///
+	/// # Errors
+	///
+	/// Returns an error if `input` is invalid for the underlying encoding. Otherwise, the error
+	/// kind is [`Length`] if the decoded data is shorter than a version and a checksum, and
+	/// [`Checksum`] if the checksum does not match. The position is then the input length.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use data_encoding::{BASE58CHECK, DecodeKind};
+	/// let (version, hash) = BASE58CHECK.decode(b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap();
+	/// assert_eq!((version, hash.len()), (0, 20));
+	/// let error = BASE58CHECK.decode(b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb").unwrap_err();
+	/// assert_eq!(error.kind, DecodeKind::Checksum);
+	/// ```
+	///
+	/// [`Length`]: enum.DecodeKind.html#variant.Length
+	/// [`Checksum`]: enum.DecodeKind.html#variant.Checksum
+	#[cfg(feature = "alloc")]
+	pub fn decode(&self, input: &[u8]) -> Result<(u8, Vec<u8>), DecodeError> {
+		let mut data = self.encoding.decode(input)?;
+		let error = |kind| DecodeError {
+			position: input.len(),
+			kind,
+		};
+		check!(error(DecodeKind::Length), data.len() >= 5);
+		let (content, checksum) = data.split_at(data.len() - 4);
+		check!(error(DecodeKind::Checksum), sha256::checksum(content) == checksum);
+		data.truncate(data.len() - 4);
+		let version = data.remove(0);
+		Ok((version, data))
+	}
+}
+
+/// Decodes from a reader
+///
+/// See [`Encoding::decode_reader`] and [`Encoding::decode_read_lenient`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct DecodeReader<'a, R> {
+	encoding: &'a Encoding,
+	reader: R,
+	/// Whether ASCII whitespace is skipped.
+	lenient: bool,
+	/// Input read but not decoded yet.
+	pending: Vec<u8>,
+	decoded: Vec<u8>,
+	/// Position of the next byte to return in `decoded`.
+	position: usize,
+	/// Number of input bytes decoded so far, to report error positions.
+	consumed: usize,
+	eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> DecodeReader<'_, R> {
+	fn fill(&mut self) -> std::io::Result<()> {
+		let mut buffer = Scratch([0u8; 1024]);
+		let len = loop {
+			match self.reader.read(&mut buffer[..]) {
+				Ok(len) => break len,
+				Err(e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+				Err(e) => return Err(e),
+			}
+		};
+		self.eof = len == 0;
+		if self.lenient {
+			self.pending.extend(buffer[..len].iter().filter(|x| !x.is_ascii_whitespace()));
+		} else {
+			self.pending.extend_from_slice(&buffer[..len]);
+		}
+		// Complete groups decode independently. Arithmetic encodings have no groups.
+		let len = if self.eof {
+			self.pending.len()
+		} else if self.encoding.is_arithmetic() {
+			0
+		} else if self.lenient {
+			floor(self.pending.len(), self.encoding.pad_group())
+		} else {
+			self.complete_groups()
+		};
+		if len == 0 {
+			return Ok(());
+		}
+		#[cfg(feature = "zeroize")]
+		zeroize::Zeroize::zeroize(&mut self.decoded);
+		self.decoded = self.encoding.decode(&self.pending[..len]).map_err(|mut error| {
+			error.position += self.consumed;
+			std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+		})?;
+		self.position = 0;
+		self.pending.copy_within(len.., 0);
+		self.pending.truncate(self.pending.len() - len);
+		self.consumed += len;
+		Ok(())
+	}
+
+	/// Returns the length of the complete groups of `pending`, with their ignored characters
+	fn complete_groups(&self) -> usize {
+		let group = self.encoding.pad_group();
+		let val = self.encoding.val();
+		let mut symbols = 0;
+		let mut len = 0;
+		for (i, &x) in self.pending.iter().enumerate() {
+			if x < 128 && val[x as usize] == IGNORE {
+				continue;
+			}
+			symbols += 1;
+			if symbols % group == 0 {
+				len = i + 1;
+			}
 		}
-		specification.use_arithmetic = self.is_arithmetic();
-		specification
+		len
 	}
+}
 
-	#[doc(hidden)]
-	#[must_use]
-	pub const fn internal_new(implementation: &'static [u8]) -> Encoding {
-		Encoding(InternalEncoding::Static(implementation))
+// The pending input and the decoded output may be secret, like a Scratch.
+#[cfg(all(feature = "std", feature = "zeroize"))]
+impl<R> Drop for DecodeReader<'_, R> {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.pending);
+		zeroize::Zeroize::zeroize(&mut self.decoded);
 	}
+}
 
-	#[doc(hidden)]
-	#[must_use]
-	pub fn internal_implementation(&self) -> &[u8] {
-		&*self.0
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for DecodeReader<'_, R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		while self.position == self.decoded.len() && !self.eof {
+			self.fill()?;
+		}
+		let len = core::cmp::min(buf.len(), self.decoded.len() - self.position);
+		buf[..len].copy_from_slice(&self.decoded[self.position..self.position + len]);
+		self.position += len;
+		Ok(len)
 	}
 }
 
-/// Encodes fragmented input to an output
+/// Encodes to a writer
 ///
-/// Use this struct if your input is in several pieces.
+/// See [`Encoding::encode_writer`].
+#[cfg(feature = "std")]
 #[derive(Debug)]
-pub struct Encoder<'a> {
+pub struct EncoderWriter<'a, W: std::io::Write> {
 	encoding: &'a Encoding,
-	output: &'a mut String,
-	buffer: [u8; 8],
-	length: usize,
+	/// Taken by [`finish`](EncoderWriter::finish).
+	writer: Option<W>,
+	/// Input of the current block, or all the input for arithmetic encodings.
+	pending: Vec<u8>,
 }
 
-impl Drop for Encoder<'_> {
-	fn drop(&mut self) {
-		self.finalize();
+#[cfg(feature = "std")]
+impl<W: std::io::Write> EncoderWriter<'_, W> {
+	/// Encodes the last partial block and returns the writer
+	///
+	/// # Errors
+	///
+	/// Returns an error if the writer fails.
+	#[allow(clippy::missing_panics_doc)] // no panic
+	pub fn finish(mut self) -> std::io::Result<W> {
+		self.write_pending()?;
+		Ok(self.writer.take().unwrap())
 	}
-}
 
-impl<'a> Encoder<'a> {
-	fn new(encoding: &'a Encoding, output: &'a mut String) -> Encoder<'a> {
-		Encoder {
-			encoding,
-			output,
-			buffer: [0u8; 8],
-			length: 0,
-		}
+	fn write_pending(&mut self) -> std::io::Result<()> {
+		let writer = self.writer.as_mut().unwrap();
+		self.encoding.encode_write(&self.pending, writer)?;
+		self.pending.clear();
+		Ok(())
 	}
+}
 
-	/// Appends the encoding of `input` to the output
-	pub fn append(&mut self, input: &[u8]) {
-		let mut input = input;
-		let (ilen, olen) = self.encoding.block_len();
-		if self.length > 0 {
-			let n = core::cmp::min(ilen - self.length, input.len());
-			self.buffer[self.length..self.length + n].copy_from_slice(&input[0..n]);
-			self.length += n;
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for EncoderWriter<'_, W> {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		if self.encoding.is_arithmetic() {
+			self.pending.extend_from_slice(buf);
+			return Ok(buf.len());
+		}
+		let mut input = buf;
+		let ilen = self.encoding.block_len().0;
+		if !self.pending.is_empty() {
+			let n = core::cmp::min(ilen - self.pending.len(), input.len());
+			self.pending.extend_from_slice(&input[..n]);
 			input = &input[n..];
-			if self.length == ilen {
-				self.encoding.encode_append(&self.buffer[0..ilen], self.output);
-				self.length = 0;
+			if self.pending.len() < ilen {
+				return Ok(buf.len());
 			}
+			self.write_pending()?;
 		}
 		let n = floor(input.len(), ilen);
-		self.encoding.encode_append(&input[0..n], self.output);
-		input = &input[n..];
-		if !input.is_empty() {
-			self.buffer[0..input.len()].copy_from_slice(input);
-			self.length = input.len();
-		}
+		self.encoding.encode_write(&input[..n], self.writer.as_mut().unwrap())?;
+		self.pending.extend_from_slice(&input[n..]);
+		Ok(buf.len())
 	}
 
-	/// Finalizes the encoding
-	pub fn finalize(&mut self) {
-		if self.length > 0 {
-			self.encoding.encode_append(&self.buffer[0..self.length], self.output);
-			self.length = 0;
+	/// Flushes the writer
+	///
+	/// A partial block is only written by [`finish`](EncoderWriter::finish), because it would
+	/// be padded.
+	fn flush(&mut self) -> std::io::Result<()> {
+		self.writer.as_mut().unwrap().flush()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Drop for EncoderWriter<'_, W> {
+	fn drop(&mut self) {
+		if self.writer.is_some() {
+			// Errors cannot be reported here, call finish to get them.
+			drop(self.write_pending());
 		}
+		// The pending input may be secret, like a Scratch.
+		#[cfg(feature = "zeroize")]
+		zeroize::Zeroize::zeroize(&mut self.pending);
 	}
 }
 
 /// Displayable version of encoded data
+///
+/// The data is encoded through a stack buffer of `N` bytes.
 #[derive(Debug)]
-pub struct Display<'a> {
+pub struct Display<'a, const N: usize = 1024> {
 	encoding: &'a Encoding,
 	input: &'a [u8],
 }
 
-impl core::fmt::Display for Display<'_> {
-	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-		let mut buffer = [0u8; 1024];
-		for chunk in self.input.chunks(floor(
-			1024 / self.encoding.block_len().1 * self.encoding.block_len().0,
-			self.encoding.block_len().0,
-		)) {
-			let len = self
-				.encoding
-				.encode_mut(chunk, &mut buffer[..self.encoding.encode_len(chunk.len()).unwrap()])
-				.unwrap();
-			write!(f, "{}", unsafe { core::str::from_utf8_unchecked(&buffer[..len]) })?;
+/// Displayable version of encoded data using a caller-provided buffer
+#[derive(Debug)]
+struct DisplayBuffered<'a> {
+	encoding: &'a Encoding,
+	input: &'a [u8],
+	scratch: core::cell::RefCell<&'a mut [u8]>,
+}
+
+/// Writes the encoding of `input` to `f` in the largest chunks that fit in `scratch`
+fn encode_fmt(
+	encoding: &Encoding,
+	input: &[u8],
+	scratch: &mut [u8],
+	f: &mut impl core::fmt::Write,
+) -> core::fmt::Result {
+	if input.is_empty() {
+		return Ok(());
+	}
+	// Arithmetic encodings without blocks are not chunked and may not fit.
+	let unchunked = encoding.is_arithmetic() && encoding.arithmetic_block_len().is_none();
+	let mut step = if let Some((ilen, olen)) = encoding.arithmetic_block_len() {
+		floor(scratch.len() / olen * ilen, ilen)
+	} else if unchunked {
+		0
+	} else {
+		let (ilen, olen) = encoding.block_len();
+		floor(scratch.len() / olen * ilen, ilen)
+	};
+	if step == 0 {
+		step = input.len();
+	}
+	for chunk in input.chunks(step) {
+		let olen = encoding.encode_len(chunk.len()).map_err(|_| core::fmt::Error)?;
+		#[cfg(feature = "alloc")]
+		if olen > scratch.len() && unchunked {
+			f.write_str(&encoding.encode(chunk))?;
+			continue;
+		}
+		if olen > scratch.len() {
+			return Err(core::fmt::Error);
 		}
+		let len = encoding.encode_mut(chunk, &mut scratch[..olen]).map_err(|_| core::fmt::Error)?;
+		// SAFETY: Encoded output is always ASCII.
+		f.write_str(unsafe { core::str::from_utf8_unchecked(&scratch[..len]) })?;
+	}
+	Ok(())
+}
+
+/// Adapts a closure to [`core::fmt::Write`] for [`Encoding::encode_chunks`]
+struct ChunkSink<F>(F);
+
+impl<F: FnMut(&str)> core::fmt::Write for ChunkSink<F> {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		(self.0)(s);
 		Ok(())
 	}
 }
 
+impl core::fmt::Display for DisplayBuffered<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		encode_fmt(self.encoding, self.input, &mut self.scratch.borrow_mut(), f)
+	}
+}
+
+impl<const N: usize> core::fmt::Display for Display<'_, N> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		encode_fmt(self.encoding, self.input, &mut Scratch([0u8; N])[..], f)
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SpecificationErrorImpl {
 	BadSize,
@@ -2124,6 +5060,9 @@ enum SpecificationErrorImpl {
 	WrapWidth(u8),
 	FromTo,
 	Undefined(u8),
+	BlockSize,
+	ConstantTime,
+	Ambiguous(u8),
 }
 
 /// Specification error
@@ -2146,11 +5085,18 @@ impl core::fmt::Display for SpecificationError {
 			SpecificationErrorImpl::WrapWidth(n) => write!(f, "wrap width not a multiple of {}", n),
 			SpecificationErrorImpl::FromTo => write!(f, "translate from/to length mismatch"),
 			SpecificationErrorImpl::Undefined(c) => write!(f, "{:?} is undefined", c as char),
+			SpecificationErrorImpl::BlockSize => write!(f, "invalid arithmetic block size"),
+			SpecificationErrorImpl::ConstantTime => {
+				write!(f, "unsupported options for constant time")
+			}
+			SpecificationErrorImpl::Ambiguous(c) => {
+				write!(f, "{:?} is a symbol in both cases", c as char)
+			}
 		}
 	}
 }
 
-impl std::error::Error for SpecificationError {
+impl core::error::Error for SpecificationError {
 	fn description(&self) -> &str {
 		match self.0 {
 			SpecificationErrorImpl::BadSize => "invalid number of symbols",
@@ -2162,6 +5108,9 @@ impl std::error::Error for SpecificationError {
 			SpecificationErrorImpl::WrapWidth(_) => "wrap width mismatch",
 			SpecificationErrorImpl::FromTo => "translate mismatch",
 			SpecificationErrorImpl::Undefined(_) => "undefined character",
+			SpecificationErrorImpl::BlockSize => "invalid arithmetic block size",
+			SpecificationErrorImpl::ConstantTime => "unsupported options for constant time",
+			SpecificationErrorImpl::Ambiguous(_) => "symbol in both cases",
 		}
 	}
 }
@@ -2185,8 +5134,15 @@ impl Specification {
 				from: String::new(),
 				to: String::new(),
 			},
+			case_insensitive: false,
 
 			use_arithmetic: false, // Par défaut, détection automatique
+			forbid_arithmetic: false,
+			allow_trailing_pad_group: false,
+			arithmetic_block_size: 0,
+			arithmetic_pad_last_block: false,
+			arithmetic_zero_block: None,
+			constant_time: false,
 		}
 	}
 
@@ -2195,8 +5151,153 @@ impl Specification {
 	/// # Errors
 	///
 	/// Returns an error if the specification is invalid.
-
 	pub fn encoding(&self) -> Result<Encoding, SpecificationError> {
+		ConstSpecification {
+			symbols: &self.symbols,
+			bit_order: self.bit_order,
+			check_trailing_bits: self.check_trailing_bits,
+			padding: self.padding,
+			padding_mode: self.padding_mode,
+			ignore: &self.ignore,
+			wrap_width: self.wrap.width,
+			wrap_separator: &self.wrap.separator,
+			translate_from: &self.translate.from,
+			translate_to: &self.translate.to,
+			case_insensitive: self.case_insensitive,
+			use_arithmetic: self.use_arithmetic,
+			forbid_arithmetic: self.forbid_arithmetic,
+			allow_trailing_pad_group: self.allow_trailing_pad_group,
+			arithmetic_block_size: self.arithmetic_block_size,
+			arithmetic_pad_last_block: self.arithmetic_pad_last_block,
+			arithmetic_zero_block: self.arithmetic_zero_block,
+			constant_time: self.constant_time,
+		}
+		.encoding()
+	}
+}
+
+/// Encoding specification usable in `const` context
+///
+/// This is a [`Specification`] with borrowed strings and the [`Wrap`] and [`Translate`] fields
+/// flattened, such that custom encodings can be built at compile time with
+/// [`Encoding::from_spec_const`] instead of at runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::{ConstSpecification, Encoding};
+/// const HEX: Encoding = Encoding::from_spec_const(&ConstSpecification {
+///     translate_from: "ABCDEF",
+///     translate_to: "abcdef",
+///     ..ConstSpecification::new("0123456789abcdef")
+/// });
+/// assert_eq!(HEX.encode(b"\xca\xfe"), "cafe");
+/// assert_eq!(HEX.decode(b"CAFE").unwrap(), b"\xca\xfe");
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)] // independent options
+pub struct ConstSpecification<'a> {
+	/// Symbols used by the encoding (see [`Specification::symbols`]).
+	pub symbols: &'a str,
+	/// Bit order (see [`Specification::bit_order`]).
+	pub bit_order: BitOrder,
+	/// Whether to check trailing bits (see [`Specification::check_trailing_bits`]).
+	pub check_trailing_bits: bool,
+	/// Padding character (see [`Specification::padding`]).
+	pub padding: Option<char>,
+	/// Padding mode (see [`Specification::padding_mode`]).
+	pub padding_mode: PaddingMode,
+	/// Characters to ignore when decoding (see [`Specification::ignore`]).
+	pub ignore: &'a str,
+	/// Wrap width (see [`Wrap::width`]).
+	pub wrap_width: usize,
+	/// Wrap separator (see [`Wrap::separator`]).
+	pub wrap_separator: &'a str,
+	/// Characters to translate from (see [`Translate::from`]).
+	pub translate_from: &'a str,
+	/// Characters to translate to (see [`Translate::to`]).
+	pub translate_to: &'a str,
+	/// Whether decoding is case-insensitive (see [`Specification::case_insensitive`]).
+	pub case_insensitive: bool,
+	/// Force use of arithmetic encoding (see [`Specification::use_arithmetic`]).
+	pub use_arithmetic: bool,
+	/// Forbid the fallback to arithmetic encoding (see [`Specification::forbid_arithmetic`]).
+	pub forbid_arithmetic: bool,
+	/// Whether to accept a trailing padding group (see
+	/// [`Specification::allow_trailing_pad_group`]).
+	pub allow_trailing_pad_group: bool,
+	/// Bytes per arithmetic block (see [`Specification::arithmetic_block_size`]).
+	pub arithmetic_block_size: usize,
+	/// Whether the last arithmetic block is padded (see
+	/// [`Specification::arithmetic_pad_last_block`]).
+	pub arithmetic_pad_last_block: bool,
+	/// Symbol for a block of zeros (see [`Specification::arithmetic_zero_block`]).
+	pub arithmetic_zero_block: Option<char>,
+	/// Whether encoding and decoding run in constant time (see
+	/// [`Specification::constant_time`]).
+	pub constant_time: bool,
+}
+
+/// Sets the value of `i` to `x`, unless it already has another value
+const fn set_value(values: &mut [u8; 128], i: u8, x: u8) -> Result<(), SpecificationError> {
+	check!(SpecificationError(SpecificationErrorImpl::NotAscii), i < 128);
+	// Setting the same value again is a no-op. In particular a character may be both in `ignore`
+	// and in the wrap separator.
+	if values[i as usize] == x {
+		return Ok(());
+	}
+	check!(SpecificationError(SpecificationErrorImpl::Duplicate(i)), values[i as usize] == INVALID);
+	values[i as usize] = x;
+	Ok(())
+}
+
+/// Sets the value of each byte of `input` to `x` (see [`set_value`])
+const fn set_values(values: &mut [u8; 128], input: &[u8], x: u8) -> Result<(), SpecificationError> {
+	let mut i = 0;
+	while i < input.len() {
+		const_try!(set_value(values, input[i], x));
+		i += 1;
+	}
+	Ok(())
+}
+
+impl<'a> ConstSpecification<'a> {
+	/// Returns a specification with the given symbols and default options
+	///
+	/// The options are the same as [`Specification::new`].
+	#[must_use]
+	pub const fn new(symbols: &'a str) -> ConstSpecification<'a> {
+		ConstSpecification {
+			symbols,
+			bit_order: MostSignificantFirst,
+			check_trailing_bits: true,
+			padding: None,
+			padding_mode: PaddingMode::Standard,
+			ignore: "",
+			wrap_width: 0,
+			wrap_separator: "",
+			translate_from: "",
+			translate_to: "",
+			case_insensitive: false,
+			use_arithmetic: false,
+			forbid_arithmetic: false,
+			allow_trailing_pad_group: false,
+			arithmetic_block_size: 0,
+			arithmetic_pad_last_block: false,
+			arithmetic_zero_block: None,
+			constant_time: false,
+		}
+	}
+
+	/// Returns the specified encoding
+	///
+	/// This is the same as [`Specification::encoding`].
+	///
+	/// # Errors
+	///
+	/// Returns an error if the specification is invalid.
+	#[allow(clippy::too_many_lines)] // one check per option
+	pub const fn encoding(&self) -> Result<Encoding, SpecificationError> {
 		let symbols = self.symbols.as_bytes();
 
 		if symbols.is_empty() {
@@ -2204,12 +5305,15 @@ impl Specification {
 		}
 
 		// Détection automatique si l'encodage arithmétique doit être utilisé
-		let use_arithmetic = self.use_arithmetic || ![2, 4, 8, 16, 32, 64].contains(&symbols.len());
+		let use_arithmetic = self.use_arithmetic
+			|| (!self.forbid_arithmetic && !matches!(symbols.len(), 2 | 4 | 8 | 16 | 32 | 64));
 
 		// Pour les bases arithmétiques, valider que les symboles sont ASCII
 		if use_arithmetic {
-			for &symbol in symbols {
-				check!(SpecificationError(SpecificationErrorImpl::NotAscii), symbol < 128);
+			let mut i = 0;
+			while i < symbols.len() {
+				check!(SpecificationError(SpecificationErrorImpl::NotAscii), symbols[i] < 128);
+				i += 1;
 			}
 		}
 
@@ -2229,23 +5333,14 @@ impl Specification {
 			}
 		};
 		let mut values = [INVALID; 128];
-		let set = |v: &mut [u8; 128], i: u8, x: u8| {
-			check!(SpecificationError(SpecificationErrorImpl::NotAscii), i < 128);
-			if v[i as usize] == x {
-				return Ok(());
-			}
-			check!(
-				SpecificationError(SpecificationErrorImpl::Duplicate(i)),
-				v[i as usize] == INVALID
-			);
-			v[i as usize] = x;
-			Ok(())
-		};
-		for (v, symbols) in symbols.iter().enumerate() {
+		let mut v = 0;
+		while v < symbols.len() {
 			#[allow(clippy::cast_possible_truncation)] // no truncation
-			set(&mut values, *symbols, v as u8)?;
+			let value = v as u8;
+			const_try!(set_value(&mut values, symbols[v], value));
+			v += 1;
 		}
-		let msb = self.bit_order == MostSignificantFirst;
+		let msb = matches!(self.bit_order, MostSignificantFirst);
 		let ctb = self.check_trailing_bits || (!use_arithmetic && 8 % bit == 0);
 		let pad = match self.padding {
 			None => None,
@@ -2254,18 +5349,22 @@ impl Specification {
 					check!(SpecificationError(SpecificationErrorImpl::ExtraPadding), 8 % bit != 0);
 				}
 				check!(SpecificationError(SpecificationErrorImpl::NotAscii), pad.len_utf8() == 1);
-				set(&mut values, pad as u8, PADDING)?;
+				const_try!(set_value(&mut values, pad as u8, PADDING));
 				Some(pad as u8)
 			}
 		};
-		for i in self.ignore.bytes() {
-			set(&mut values, i, IGNORE)?;
+		if self.allow_trailing_pad_group && pad.is_none() {
+			check!(
+				SpecificationError(SpecificationErrorImpl::Duplicate(b'=')),
+				values[b'=' as usize] == INVALID
+			);
 		}
-		let wrap = if self.wrap.separator.is_empty() || self.wrap.width == 0 {
+		const_try!(set_values(&mut values, self.ignore.as_bytes(), IGNORE));
+		let wrap = if self.wrap_separator.is_empty() || self.wrap_width == 0 {
 			None
 		} else {
-			let col = self.wrap.width;
-			let end = self.wrap.separator.as_bytes();
+			let col = self.wrap_width;
+			let end = self.wrap_separator.as_bytes();
 			check!(SpecificationError(SpecificationErrorImpl::WrapSeparator), end.len() <= 15);
 			check!(
 				SpecificationError(SpecificationErrorImpl::WrapLength),
@@ -2274,32 +5373,93 @@ impl Specification {
 			#[allow(clippy::cast_possible_truncation)] // no truncation
 			let col = col as u8;
 			#[allow(clippy::cast_possible_truncation)] // no truncation
-			let dec = dec(bit as usize) as u8;
-			check!(SpecificationError(SpecificationErrorImpl::WrapWidth(dec)), col % dec == 0);
-			for &i in end {
-				set(&mut values, i, IGNORE)?;
-			}
+			let enc = enc(bit as usize) as u8;
+			// Unpadded output is not block-aligned anyway, so lines may end anywhere.
+			check!(
+				SpecificationError(SpecificationErrorImpl::WrapWidth(enc)),
+				pad.is_none() || col % enc == 0
+			);
+			const_try!(set_values(&mut values, end, IGNORE));
 			Some((col, end))
 		};
-		let from = self.translate.from.as_bytes();
-		let to = self.translate.to.as_bytes();
+		if self.case_insensitive {
+			let mut i = 0;
+			while i < symbols.len() {
+				let symbol = symbols[i];
+				if symbol.is_ascii_alphabetic() {
+					// Flipping this bit changes the case of ASCII letters.
+					let other = symbol ^ 0x20;
+					check!(
+						SpecificationError(SpecificationErrorImpl::Ambiguous(symbol)),
+						values[other as usize] >= INVALID
+					);
+					let value = values[symbol as usize];
+					const_try!(set_value(&mut values, other, value));
+				}
+				i += 1;
+			}
+		}
+		let from = self.translate_from.as_bytes();
+		let to = self.translate_to.as_bytes();
 		check!(SpecificationError(SpecificationErrorImpl::FromTo), from.len() == to.len());
-		for i in 0..from.len() {
+		let mut i = 0;
+		while i < from.len() {
 			check!(SpecificationError(SpecificationErrorImpl::NotAscii), to[i] < 128);
 			let v = values[to[i] as usize];
 			check!(SpecificationError(SpecificationErrorImpl::Undefined(to[i])), v != INVALID);
-			set(&mut values, from[i], v)?;
+			const_try!(set_value(&mut values, from[i], v));
+			i += 1;
 		}
 		let mut encoding = [INVALID; 531];
-		if use_arithmetic {
-			encoding[0..symbols.len()].copy_from_slice(symbols);
-		} else {
-			for i in 0..256 {
+		let mut i = 0;
+		while i < 256 {
+			if !use_arithmetic {
 				encoding[i] = symbols[i % symbols.len()];
+			} else if i < symbols.len() {
+				encoding[i] = symbols[i];
 			}
+			i += 1;
+		}
+		let mut i = 0;
+		while i < 128 {
+			encoding[256 + i] = values[i];
+			i += 1;
+		}
+		if self.allow_trailing_pad_group && !use_arithmetic {
+			encoding[511] = INVALID | 0x01;
+		}
+		if self.arithmetic_block_size != 0 {
+			check!(
+				SpecificationError(SpecificationErrorImpl::BlockSize),
+				use_arithmetic && self.arithmetic_block_size <= arithmetic::MAX_BLOCK_SIZE
+			);
+			#[allow(clippy::cast_possible_truncation)] // no truncation
+			let block = self.arithmetic_block_size as u8;
+			encoding[510] = block | (self.arithmetic_pad_last_block as u8) << 5;
+		} else {
+			check!(
+				SpecificationError(SpecificationErrorImpl::BlockSize),
+				!self.arithmetic_pad_last_block && self.arithmetic_zero_block.is_none()
+			);
+		}
+		if let Some(zero) = self.arithmetic_zero_block {
+			check!(SpecificationError(SpecificationErrorImpl::NotAscii), zero.is_ascii());
+			check!(
+				SpecificationError(SpecificationErrorImpl::Duplicate(zero as u8)),
+				values[zero as usize] == INVALID
+			);
+			encoding[509] = zero as u8;
+		}
+		if self.constant_time {
+			check!(
+				SpecificationError(SpecificationErrorImpl::ConstantTime),
+				!use_arithmetic
+					&& self.ignore.is_empty()
+					&& wrap.is_none()
+					&& (pad.is_none() || matches!(self.padding_mode, PaddingMode::Standard))
+			);
+			encoding[511] |= 0x02;
 		}
-		encoding[256..512].copy_from_slice(&[INVALID; 256]);
-		encoding[256..384].copy_from_slice(&values);
 		if use_arithmetic {
 			encoding[512] = symbols.len() as u8;
 		} else {
@@ -2323,7 +5483,10 @@ impl Specification {
 			encoding[513] |= 0x10;
 		}
 
+		// Without a padding character, the output is never block-aligned.
 		let mode_bits = match self.padding_mode {
+			_ if pad.is_none() => 0,
+
 			PaddingMode::None => 0,
 
 			PaddingMode::Standard => 1,
@@ -2338,7 +5501,11 @@ impl Specification {
 		if let Some((col, end)) = wrap {
 			encoding[514] = col;
 			encoding[515] = end.len() as u8;
-			encoding[516..516 + end.len()].copy_from_slice(end);
+			let mut i = 0;
+			while i < end.len() {
+				encoding[516 + i] = end[i];
+				i += 1;
+			}
 		} else {
 			encoding[514] = 0;
 			encoding[515] = 0;
@@ -2347,6 +5514,121 @@ impl Specification {
 	}
 }
 
+/// Serializes as the [`Specification`] of the encoding
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Encoding {
+	fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.specification().serialize(serializer)
+	}
+}
+
+/// Deserializes a [`Specification`] and builds its encoding
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Encoding {
+	fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Encoding, D::Error> {
+		Specification::deserialize(deserializer)?.encoding().map_err(::serde::de::Error::custom)
+	}
+}
+
+impl TryFrom<&Specification> for Encoding {
+	type Error = SpecificationError;
+
+	fn try_from(specification: &Specification) -> Result<Encoding, SpecificationError> {
+		specification.encoding()
+	}
+}
+
+impl TryFrom<Specification> for Encoding {
+	type Error = SpecificationError;
+
+	fn try_from(specification: Specification) -> Result<Encoding, SpecificationError> {
+		specification.encoding()
+	}
+}
+
+/// Returns `blocks * len + extra`, for the `const` encoding lengths below
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+const fn blocks_len(blocks: usize, len: usize, extra: usize) -> usize {
+	match blocks.checked_mul(len) {
+		Some(x) => match x.checked_add(extra) {
+			Some(x) => x,
+			None => panic!("encoding length overflow"),
+		},
+		None => panic!("encoding length overflow"),
+	}
+}
+
+/// Returns the length of [`HEXLOWER`] or [`HEXUPPER`] encoding `n` bytes
+///
+/// This is a `const fn` to size arrays:
+///
+/// ```rust
+/// use data_encoding::{hex_encode_len, HEXLOWER};
+/// let mut buffer = [0u8; hex_encode_len(4)];
+/// HEXLOWER.encode_mut(b"\x01\x02\x03\x04", &mut buffer).unwrap();
+/// assert_eq!(&buffer, b"01020304");
+/// ```
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+#[must_use]
+pub const fn hex_encode_len(n: usize) -> usize {
+	blocks_len(n, 2, 0)
+}
+
+/// Returns the length of [`BASE32`] encoding `n` bytes
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+#[must_use]
+pub const fn base32_encode_len(n: usize) -> usize {
+	blocks_len(n.div_ceil(5), 8, 0)
+}
+
+/// Returns the length of [`BASE32_NOPAD`] encoding `n` bytes
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+#[must_use]
+pub const fn base32_nopad_encode_len(n: usize) -> usize {
+	blocks_len(n / 5, 8, (n % 5 * 8).div_ceil(5))
+}
+
+/// Returns the length of [`BASE64`] encoding `n` bytes
+///
+/// This is a `const fn` to size arrays:
+///
+/// ```rust
+/// use data_encoding::{base64_encode_len, BASE64};
+/// let mut buffer = [0u8; base64_encode_len(5)];
+/// BASE64.encode_mut(b"Hello", &mut buffer).unwrap();
+/// assert_eq!(&buffer, b"SGVsbG8=");
+/// ```
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+#[must_use]
+pub const fn base64_encode_len(n: usize) -> usize {
+	blocks_len(n.div_ceil(3), 4, 0)
+}
+
+/// Returns the length of [`BASE64_NOPAD`] encoding `n` bytes
+///
+/// # Panics
+///
+/// Panics if the result overflows.
+#[must_use]
+pub const fn base64_nopad_encode_len(n: usize) -> usize {
+	blocks_len(n / 3, 4, (n % 3 * 4).div_ceil(3))
+}
+
 /// Lowercase hexadecimal encoding
 pub const HEXLOWER: Encoding = Encoding::internal_new(data::HEXLOWER_IMPL);
 
@@ -2359,6 +5641,13 @@ pub const HEXUPPER: Encoding = Encoding::internal_new(data::HEXUPPER_IMPL);
 /// Uppercase hexadecimal encoding with case-insensitive decoding
 pub const HEXUPPER_PERMISSIVE: Encoding = Encoding::internal_new(data::HEXUPPER_PERMISSIVE_IMPL);
 
+/// Lowercase hexadecimal encoding in constant time
+///
+/// This is [`HEXLOWER`] with [`constant_time`] set, for secrets like keys.
+///
+/// [`constant_time`]: struct.Specification.html#structfield.constant_time
+pub const HEXLOWER_CT: Encoding = Encoding::internal_new(data::HEXLOWER_CT_IMPL);
+
 /// Padded base32 encoding
 pub const BASE32: Encoding = Encoding::internal_new(data::BASE32_IMPL);
 
@@ -2383,12 +5672,40 @@ pub const BASE32_DNSSEC: Encoding = Encoding::internal_new(data::BASE32_DNSSEC_I
 /// DNSCurve base32 encoding
 pub const BASE32_DNSCURVE: Encoding = Encoding::internal_new(data::BASE32_DNSCURVE_IMPL);
 
+/// Crockford base32 encoding
+///
+/// Decoding is case-insensitive, reads `I` and `L` as `1` and `O` as `0`, and ignores hyphens.
+/// See [`encode_check_symbol`] for the optional check symbol.
+///
+/// [`encode_check_symbol`]: struct.Encoding.html#method.encode_check_symbol
+pub const BASE32_CROCKFORD: Encoding = Encoding::internal_new(data::BASE32_CROCKFORD_IMPL);
+
 /// Padded base64 encoding
 pub const BASE64: Encoding = Encoding::internal_new(data::BASE64_IMPL);
 
 /// Unpadded base64 encoding
 pub const BASE64_NOPAD: Encoding = Encoding::internal_new(data::BASE64_NOPAD_IMPL);
 
+/// Padded base64 encoding in constant time
+///
+/// This is [`BASE64`] with [`constant_time`] set, for secrets like keys.
+///
+/// [`constant_time`]: struct.Specification.html#structfield.constant_time
+pub const BASE64_CT: Encoding = Encoding::internal_new(data::BASE64_CT_IMPL);
+
+/// Padded base64 encoding decoding both the standard and url alphabets
+///
+/// This encoding is decode-permissive and encode-standard: `-` decodes like `+` and `_` like `/`,
+/// so standard and url-safe inputs decode to the same bytes, but the output always uses the
+/// standard alphabet, like [`BASE64`].
+///
+/// ```rust
+/// use data_encoding::BASE64_ANY;
+/// assert_eq!(BASE64_ANY.decode(b"+/8=").unwrap(), BASE64_ANY.decode(b"-_8=").unwrap());
+/// assert_eq!(BASE64_ANY.encode(&[0xfb, 0xff]), "+/8=");
+/// ```
+pub const BASE64_ANY: Encoding = Encoding::internal_new(data::BASE64_ANY_IMPL);
+
 /// MIME base64 encoding
 pub const BASE64_MIME: Encoding = Encoding::internal_new(data::BASE64_MIME_IMPL);
 
@@ -2402,12 +5719,45 @@ pub const BASE64URL: Encoding = Encoding::internal_new(data::BASE64URL_IMPL);
 /// Unpadded base64url encoding
 pub const BASE64URL_NOPAD: Encoding = Encoding::internal_new(data::BASE64URL_NOPAD_IMPL);
 
+/// Padded base64url encoding, named like in the `base64` crate
+///
+/// This is the same encoding as [`BASE64URL`].
+pub const BASE64_URL_SAFE: Encoding = BASE64URL;
+
+/// Unpadded base64url encoding, named like in the `base64` crate
+///
+/// This is the same encoding as [`BASE64URL_NOPAD`].
+pub const BASE64_URL_SAFE_NO_PAD: Encoding = BASE64URL_NOPAD;
+
 /// Base58 encoding (Bitcoin alphabet)
 pub const BASE58: Encoding = Encoding::internal_new(data::BASE58_IMPL);
 
+/// Base58Check encoding (Bitcoin addresses)
+///
+/// This is [`BASE58`] with a version byte and a 4-byte double SHA-256 checksum.
+pub const BASE58CHECK: CheckedEncoding<'static> = BASE58.checked();
+
 /// Base62 encoding
 pub const BASE62: Encoding = Encoding::internal_new(data::BASE62_IMPL);
 
+/// Z85 encoding (ZeroMQ)
+///
+/// Blocks of 4 bytes are encoded as 5 symbols. ZeroMQ only defines whole blocks, and a shorter
+/// last block is padded and truncated like [`ASCII85`].
+pub const Z85: Encoding = Encoding::internal_new(data::Z85_IMPL);
+
+/// Base85 encoding (RFC 1924 alphabet)
+///
+/// This is the encoding of Git binary patches and Python's `b85encode`: blocks of 4 bytes are
+/// encoded as 5 symbols, and a shorter last block is padded and truncated.
+pub const BASE85: Encoding = Encoding::internal_new(data::BASE85_IMPL);
+
+/// Ascii85 encoding (Adobe, btoa)
+///
+/// Blocks of 4 bytes are encoded as 5 symbols, or `z` for zero bytes, and a shorter last block
+/// is padded and truncated. The `<~` and `~>` delimiters of Adobe are not part of the encoding.
+pub const ASCII85: Encoding = Encoding::internal_new(data::ASCII85_IMPL);
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -2444,6 +5794,61 @@ mod tests {
 		assert_eq!(input, decoded.as_slice());
 	}
 
+	#[test]
+	fn test_vector_kernels() {
+		// Input bytes per vector when encoding and decoding, for hexadecimal then base64.
+		#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+		let steps = avx2::available().then_some(([32, 24], [64, 32]));
+		#[cfg(target_arch = "aarch64")]
+		let steps = neon::available().then_some(([16, 48], [32, 64]));
+		#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+		let steps = None::<([usize; 2], [usize; 2])>;
+		let Some((encode_steps, decode_steps)) = steps else { return };
+		let encode = |base: &Encoding, input: &[u8], output: &mut [u8]| match base.bit() {
+			4 => encode_vector::<B4, Bt>(base.sym(), input, output),
+			_ => encode_vector::<B6, Bt>(base.sym(), input, output),
+		};
+		let decode = |base: &Encoding, input: &[u8], output: &mut [u8]| match base.bit() {
+			4 => decode_vector::<B4, Bt>(base.val(), input, output),
+			_ => decode_vector::<B6, Bt>(base.val(), input, output),
+		};
+
+		let mut spec = BASE64.specification();
+		spec.symbols = spec.symbols.chars().rev().collect();
+		let reversed = spec.encoding().unwrap();
+		let input: Vec<u8> = (0..300).map(|i| (i * 37 % 256) as u8).collect();
+		let bases = [&HEXLOWER, &HEXUPPER_PERMISSIVE, &BASE64, &BASE64URL, &reversed];
+		for base in bases {
+			let k = usize::from(base.bit() == 6);
+			let (ilen, olen) = [(1, 2), (3, 4)][k];
+			for len in [0, 23, 24, 27, 28, 31, 32, 47, 48, 63, 64, 100, 300] {
+				let input = &input[..len];
+				let mut output = vec![0u8; len / ilen * olen];
+				let n = encode(base, input, &mut output);
+				assert_eq!(n % ilen, 0);
+				assert!(len < 2 * encode_steps[k] || n > len - 2 * encode_steps[k], "{len}");
+				let expected = base.encode(&input[..n]);
+				assert_eq!(&output[..n / ilen * olen], expected.as_bytes(), "{len}");
+			}
+			// Whole vectors followed by a partial one.
+			let encoded = base.encode(&input[..[100, 200][k]]);
+			let encoded = encoded.as_bytes();
+			let step = decode_steps[k];
+			let mut output = vec![0u8; encoded.len()];
+			let n = decode(base, encoded, &mut output);
+			assert_eq!(n, encoded.len() / step * step);
+			assert_eq!(&output[..n / olen * ilen], &input[..n / olen * ilen]);
+			for position in 0..encoded.len() {
+				for invalid in [b'!', b'=', b'\n', 0x80, 0xff] {
+					let mut encoded = encoded.to_vec();
+					encoded[position] = invalid;
+					let n = decode(base, &encoded, &mut output);
+					assert_eq!(n, position / step * step);
+				}
+			}
+		}
+	}
+
 	#[test]
 	fn test_base58_leaders() {
 		let input = b"\0\0\0Hello";
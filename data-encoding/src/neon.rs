@@ -0,0 +1,171 @@
+//! NEON kernels for hexadecimal and base64 with most significant bit first.
+//!
+//! This is the aarch64 counterpart of the AVX2 kernels. Symbols are mapped with `tbl` on up to 64
+//! bytes of the tables, which works for any alphabet, and the interleaving loads and stores split
+//! and merge symbols without shuffles.
+//!
+//! Kernels only handle whole vectors and return how much input they processed. The rest is left
+//! to the scalar code, which is also the one reporting errors.
+
+use core::arch::aarch64 as neon;
+
+/// Whether the CPU supports NEON
+///
+/// NEON is part of the baseline aarch64 targets, but it may be disabled for some of them. Without
+/// the `std` feature, there is no runtime detection and this follows the target feature.
+pub(crate) fn available() -> bool {
+	#[cfg(feature = "std")]
+	{
+		std::arch::is_aarch64_feature_detected!("neon")
+	}
+	#[cfg(not(feature = "std"))]
+	{
+		cfg!(target_feature = "neon")
+	}
+}
+
+/// Encodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 16 bytes. The caller must check [`available`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn encode_hex(input: &[u8], output: &mut [u8], sym: &[u8; 256]) -> usize {
+	let n = core::cmp::min(input.len() / 16, output.len() / 32);
+	unsafe {
+		let table = neon::vld1q_u8(sym.as_ptr());
+		for i in 0..n {
+			let x = neon::vld1q_u8(input[16 * i..16 * i + 16].as_ptr());
+			let high = neon::vqtbl1q_u8(table, neon::vshrq_n_u8(x, 4));
+			let low = neon::vqtbl1q_u8(table, neon::vandq_u8(x, neon::vdupq_n_u8(0x0f)));
+			let output = &mut output[32 * i..32 * i + 32];
+			neon::vst2q_u8(output.as_mut_ptr(), neon::uint8x16x2_t(high, low));
+		}
+	}
+	16 * n
+}
+
+/// Encodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 48 bytes. The caller must check [`available`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn encode_base64(input: &[u8], output: &mut [u8], sym: &[u8; 256]) -> usize {
+	let n = core::cmp::min(input.len() / 48, output.len() / 64);
+	unsafe {
+		let table = neon::vld1q_u8_x4(sym.as_ptr());
+		let mask = neon::vdupq_n_u8(0x3f);
+		for i in 0..n {
+			let neon::uint8x16x3_t(b0, b1, b2) =
+				neon::vld3q_u8(input[48 * i..48 * i + 48].as_ptr());
+			let x0 = neon::vshrq_n_u8(b0, 2);
+			let x1 = neon::vandq_u8(neon::vsliq_n_u8(neon::vshrq_n_u8(b1, 4), b0, 4), mask);
+			let x2 = neon::vandq_u8(neon::vsliq_n_u8(neon::vshrq_n_u8(b2, 6), b1, 2), mask);
+			let x3 = neon::vandq_u8(b2, mask);
+			let symbols = neon::uint8x16x4_t(
+				neon::vqtbl4q_u8(table, x0),
+				neon::vqtbl4q_u8(table, x1),
+				neon::vqtbl4q_u8(table, x2),
+				neon::vqtbl4q_u8(table, x3),
+			);
+			neon::vst4q_u8(output[64 * i..64 * i + 64].as_mut_ptr(), symbols);
+		}
+	}
+	48 * n
+}
+
+/// Maps symbols to their values
+///
+/// Non-symbols (invalid, ignored, or padding) have their high bit set. Non-ASCII bytes map to 0
+/// and must be checked separately.
+#[target_feature(enable = "neon")]
+#[inline]
+unsafe fn map(x: neon::uint8x16_t, tables: &[neon::uint8x16x4_t; 2]) -> neon::uint8x16_t {
+	// Those intrinsics are only safe in NEON functions since Rust 1.87.
+	#[allow(unused_unsafe)]
+	unsafe {
+		// Indices out of the table give 0 for the first lookup and are kept by the second.
+		let y = neon::vqtbl4q_u8(tables[0], x);
+		neon::vqtbx4q_u8(y, tables[1], neon::vsubq_u8(x, neon::vdupq_n_u8(64)))
+	}
+}
+
+/// Returns whether a byte of `x` has its high bit set
+#[target_feature(enable = "neon")]
+#[inline]
+unsafe fn any_high(x: neon::uint8x16_t) -> bool {
+	// Those intrinsics are only safe in NEON functions since Rust 1.87.
+	#[allow(unused_unsafe)]
+	unsafe {
+		neon::vmaxvq_u8(x) >= 128
+	}
+}
+
+/// Returns the length of the prefix of `input` made of symbols
+///
+/// The prefix is a multiple of 16 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn symbols(input: &[u8], val: &[u8; 128]) -> usize {
+	let n = input.len() / 16;
+	unsafe {
+		let tables = [neon::vld1q_u8_x4(val.as_ptr()), neon::vld1q_u8_x4(val[64..].as_ptr())];
+		for i in 0..n {
+			let x = neon::vld1q_u8(input[16 * i..16 * i + 16].as_ptr());
+			if any_high(neon::vorrq_u8(x, map(x, &tables))) {
+				return 16 * i;
+			}
+		}
+	}
+	16 * n
+}
+
+/// Decodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 32 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn decode_hex(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> usize {
+	let n = core::cmp::min(input.len() / 32, output.len() / 16);
+	unsafe {
+		let tables = [neon::vld1q_u8_x4(val.as_ptr()), neon::vld1q_u8_x4(val[64..].as_ptr())];
+		for i in 0..n {
+			let neon::uint8x16x2_t(high, low) =
+				neon::vld2q_u8(input[32 * i..32 * i + 32].as_ptr());
+			let symbols = neon::vorrq_u8(high, low);
+			let (high, low) = (map(high, &tables), map(low, &tables));
+			if any_high(neon::vorrq_u8(symbols, neon::vorrq_u8(high, low))) {
+				return 32 * i;
+			}
+			let y = neon::vsliq_n_u8(low, high, 4);
+			neon::vst1q_u8(output[16 * i..16 * i + 16].as_mut_ptr(), y);
+		}
+	}
+	32 * n
+}
+
+/// Decodes a prefix of `input` in `output` and returns its length
+///
+/// The prefix is a multiple of 64 bytes and stops before the first vector with a non-symbol. The
+/// caller must check [`available`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn decode_base64(input: &[u8], output: &mut [u8], val: &[u8; 128]) -> usize {
+	let n = core::cmp::min(input.len() / 64, output.len() / 48);
+	unsafe {
+		let tables = [neon::vld1q_u8_x4(val.as_ptr()), neon::vld1q_u8_x4(val[64..].as_ptr())];
+		for i in 0..n {
+			let neon::uint8x16x4_t(x0, x1, x2, x3) =
+				neon::vld4q_u8(input[64 * i..64 * i + 64].as_ptr());
+			let symbols = neon::vorrq_u8(neon::vorrq_u8(x0, x1), neon::vorrq_u8(x2, x3));
+			let (x0, x1) = (map(x0, &tables), map(x1, &tables));
+			let (x2, x3) = (map(x2, &tables), map(x3, &tables));
+			let values = neon::vorrq_u8(neon::vorrq_u8(x0, x1), neon::vorrq_u8(x2, x3));
+			if any_high(neon::vorrq_u8(symbols, values)) {
+				return 64 * i;
+			}
+			let b0 = neon::vorrq_u8(neon::vshlq_n_u8(x0, 2), neon::vshrq_n_u8(x1, 4));
+			let b1 = neon::vorrq_u8(neon::vshlq_n_u8(x1, 4), neon::vshrq_n_u8(x2, 2));
+			let b2 = neon::vorrq_u8(neon::vshlq_n_u8(x2, 6), x3);
+			let output = &mut output[48 * i..48 * i + 48];
+			neon::vst3q_u8(output.as_mut_ptr(), neon::uint8x16x3_t(b0, b1, b2));
+		}
+	}
+	64 * n
+}
@@ -0,0 +1,282 @@
+//! PEM textual encoding
+//!
+//! This implements the textual encoding of [RFC 7468] used for certificates and keys, with the
+//! encapsulated headers of [RFC 1421] still found in some legacy private keys. A document is a
+//! `-----BEGIN LABEL-----` line, optional `Name: value` header lines followed by an empty line,
+//! the data in base64 wrapped at 64 columns, and a `-----END LABEL-----` line.
+//!
+//! Decoding follows either the strict or the lax grammar of RFC 7468, see [`Mode`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::pem::{self, Mode};
+//! let encoded = pem::encode("DATA", b"hello").unwrap();
+//! assert_eq!(encoded, "-----BEGIN DATA-----\naGVsbG8=\n-----END DATA-----\n");
+//! let pem = pem::decode("text\n-----BEGIN DATA-----\naGVs bG8=\r\n-----END DATA-----", Mode::Lax)
+//!     .unwrap();
+//! assert_eq!(pem.label, "DATA");
+//! assert_eq!(pem.data, b"hello");
+//! ```
+//!
+//! [RFC 7468]: https://tools.ietf.org/html/rfc7468
+//! [RFC 1421]: https://tools.ietf.org/html/rfc1421
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+	BASE64, ConstSpecification, DecodeError, DecodeKind, EncodeError, EncodeKind, Encoding,
+};
+
+/// Number of symbols per line
+const LINE: usize = 64;
+
+/// Prefix of the line before the data
+const BEGIN: &[u8] = b"-----BEGIN ";
+
+/// Prefix of the line after the data
+const END: &[u8] = b"-----END ";
+
+/// Suffix of the lines around the data
+const DASHES: &[u8] = b"-----";
+
+/// Encoding of the data in strict mode
+const STRICT: Encoding = Encoding::from_spec_const(&ConstSpecification {
+	padding: Some('='),
+	ignore: "\r\n",
+	..ConstSpecification::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+});
+
+/// Encoding of the data in lax mode
+const LAX: Encoding = Encoding::from_spec_const(&ConstSpecification {
+	padding: Some('='),
+	ignore: " \t\r\n\x0b\x0c",
+	..ConstSpecification::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+});
+
+/// Decoding mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Mode {
+	/// Only accepts the output of [`encode`], up to `\r\n` line endings.
+	///
+	/// The document must start with the `BEGIN` line and end with the `END` line. Data lines
+	/// must have 64 symbols, except the last one which may be shorter.
+	Strict,
+	/// Accepts whitespace anywhere in the data and lines of any length.
+	///
+	/// Text before the `BEGIN` line and after the `END` line is ignored, as well as whitespace
+	/// at the end of lines.
+	Lax,
+}
+
+/// Decoded PEM document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem {
+	/// Label of the `BEGIN` and `END` lines, for example `CERTIFICATE`.
+	pub label: String,
+	/// Header lines, as names and values without surrounding whitespace.
+	///
+	/// Folded values, continued on lines starting with whitespace, are unfolded.
+	pub headers: Vec<(String, String)>,
+	/// Decoded data.
+	pub data: Vec<u8>,
+}
+
+impl Pem {
+	/// Returns the document encoded with its headers
+	///
+	/// # Errors
+	///
+	/// Returns an error with kind [`Label`] if the label is invalid (see [`encode`]), a header
+	/// name is empty or has characters outside `!` to `~` or a colon, or a header value has a
+	/// line break.
+	///
+	/// [`Label`]: crate::EncodeKind::Label
+	pub fn encode(&self) -> Result<String, EncodeError> {
+		write(&self.label, &self.headers, &self.data)
+	}
+}
+
+/// Returns whether `label` is a sequence of printable characters other than `-` separated by a
+/// single space or hyphen
+fn valid_label(label: &[u8]) -> bool {
+	label.is_empty()
+		|| label
+			.split(|&x| x == b' ' || x == b'-')
+			.all(|part| !part.is_empty() && part.iter().all(|&x| (33..=126).contains(&x)))
+}
+
+/// Returns whether `name` is a valid header name
+fn valid_name(name: &str) -> bool {
+	!name.is_empty() && name.bytes().all(|x| (33..=126).contains(&x) && x != b':')
+}
+
+/// Returns whether `x` is whitespace in the lax grammar
+fn is_space(x: u8) -> bool {
+	b" \t\r\n\x0b\x0c".contains(&x)
+}
+
+/// Returns the end of the content and the start of the next line for the line at `start`
+///
+/// The content excludes the line ending, and also trailing whitespace in lax mode.
+fn line(input: &[u8], start: usize, mode: Mode) -> (usize, usize) {
+	let (mut end, next) = match input[start..].iter().position(|&x| x == b'\n') {
+		Some(i) => (start + i, start + i + 1),
+		None => (input.len(), input.len()),
+	};
+	if end > start && input[end - 1] == b'\r' {
+		end -= 1;
+	}
+	if mode == Mode::Lax {
+		while end > start && is_space(input[end - 1]) {
+			end -= 1;
+		}
+	}
+	(end, next)
+}
+
+/// Returns the label of the boundary line `input[start .. end]` starting with `prefix`
+fn boundary<'a>(input: &'a str, start: usize, end: usize, prefix: &[u8]) -> Option<&'a str> {
+	let label = input.as_bytes()[start..end].strip_prefix(prefix)?.strip_suffix(DASHES)?;
+	valid_label(label).then(|| &input[start + prefix.len()..end - DASHES.len()])
+}
+
+fn write(label: &str, headers: &[(String, String)], data: &[u8]) -> Result<String, EncodeError> {
+	let error = EncodeError {
+		kind: EncodeKind::Label,
+	};
+	check!(error, valid_label(label.as_bytes()));
+	let mut output = String::new();
+	output.push_str("-----BEGIN ");
+	output.push_str(label);
+	output.push_str("-----\n");
+	for (name, value) in headers {
+		check!(error, valid_name(name) && !value.contains(['\r', '\n']));
+		output.push_str(name);
+		output.push_str(": ");
+		output.push_str(value);
+		output.push('\n');
+	}
+	if !headers.is_empty() {
+		output.push('\n');
+	}
+	// Each chunk fills exactly one line, such that padding only appears on the last one.
+	for chunk in data.chunks(LINE / 4 * 3) {
+		BASE64.encode_append(chunk, &mut output);
+		output.push('\n');
+	}
+	output.push_str("-----END ");
+	output.push_str(label);
+	output.push_str("-----\n");
+	Ok(output)
+}
+
+/// Returns `data` encoded as a PEM document with label `label`
+///
+/// The data is encoded in base64 with 64 symbols per line, and lines end with `\n`. Use
+/// [`Pem::encode`] to also encode header lines.
+///
+/// # Errors
+///
+/// Returns an error with kind [`Label`] if `label` has characters outside `!` to `~` other than
+/// single spaces or hyphens between them.
+///
+/// [`Label`]: crate::EncodeKind::Label
+pub fn encode(label: &str, data: &[u8]) -> Result<String, EncodeError> {
+	write(label, &[], data)
+}
+
+/// Returns the label, the header lines, and the data of the PEM document `input`
+///
+/// The headers are only parsed if the line after the `BEGIN` line has a colon, in which case
+/// they must be followed by an empty line.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid:
+/// - [`Boundary`] if the `BEGIN` or `END` line is missing or invalid, or if their labels differ,
+/// - [`Symbol`] if a header line has no colon or an invalid name,
+/// - [`Length`] if a data line has an invalid length or if there is text after the `END` line,
+///   in strict mode,
+/// - any error of [`BASE64`] decoding the data.
+///
+/// [`Boundary`]: crate::DecodeKind::Boundary
+/// [`Symbol`]: crate::DecodeKind::Symbol
+/// [`Length`]: crate::DecodeKind::Length
+pub fn decode(input: &str, mode: Mode) -> Result<Pem, DecodeError> {
+	let text = input.as_bytes();
+	let error = |position, kind| DecodeError {
+		position,
+		kind,
+	};
+	let mut start = 0;
+	if mode == Mode::Lax {
+		loop {
+			let begin = start + text[start..].iter().take_while(|&&x| is_space(x)).count();
+			if text[begin..].starts_with(BEGIN) {
+				start = begin;
+				break;
+			}
+			check!(error(text.len(), DecodeKind::Boundary), begin < text.len());
+			start = line(text, begin, mode).1;
+		}
+	}
+	let (end, mut next) = line(text, start, mode);
+	let label = boundary(input, start, end, BEGIN).ok_or(error(start, DecodeKind::Boundary))?;
+	let mut headers: Vec<(String, String)> = Vec::new();
+	let (end, _) = line(text, next, mode);
+	if !text[next..].starts_with(DASHES) && text[next..end].contains(&b':') {
+		loop {
+			check!(error(next, DecodeKind::Boundary), next < text.len());
+			let (end, after) = line(text, next, mode);
+			if end == next {
+				next = after;
+				break;
+			}
+			let content = &input[next..end];
+			let symbol = error(next, DecodeKind::Symbol);
+			if content.starts_with([' ', '\t']) {
+				headers.last_mut().ok_or(symbol)?.1.push_str(content);
+			} else {
+				let (name, value) = content.split_once(':').ok_or(symbol)?;
+				check!(symbol, valid_name(name));
+				headers.push((name.to_owned(), value.to_owned()));
+			}
+			next = after;
+		}
+		for (_, value) in &mut headers {
+			*value = value.trim_ascii().to_owned();
+		}
+	}
+	let body = next;
+	let mut short = false;
+	while !text[next..].starts_with(DASHES) {
+		check!(error(text.len(), DecodeKind::Boundary), next < text.len());
+		let (end, after) = line(text, next, mode);
+		if mode == Mode::Strict {
+			// Only the last line may be shorter, but not empty.
+			check!(error(next, DecodeKind::Length), !short && end > next);
+			check!(error(next + LINE, DecodeKind::Length), end - next <= LINE);
+			short = end - next < LINE;
+		}
+		next = after;
+	}
+	let (end, after) = line(text, next, mode);
+	check!(error(next, DecodeKind::Boundary), boundary(input, next, end, END) == Some(label));
+	check!(error(after, DecodeKind::Length), mode == Mode::Lax || after == text.len());
+	let encoding = match mode {
+		Mode::Strict => &STRICT,
+		Mode::Lax => &LAX,
+	};
+	let data = encoding.decode(&text[body..next]).map_err(|mut error| {
+		error.position += body;
+		error
+	})?;
+	Ok(Pem {
+		label: label.to_owned(),
+		headers,
+		data,
+	})
+}
@@ -0,0 +1,104 @@
+//! Conversion of digits between bases
+//!
+//! This exposes the big integer arithmetic behind [`BASE58`] and the other arithmetic encodings,
+//! for encodings not covered by a [`Specification`] (for example base36 serial numbers). Digits
+//! are bytes holding their value, most significant first, and the mapping to symbols is left to
+//! the caller.
+//!
+//! Leading zero digits are kept one for one, like the leading zero bytes of [`BASE58`], such
+//! that byte strings with leading zeros round-trip.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::radix;
+//! let input = [0x00, 0x01, 0x00];
+//! let mut digits = [0; 8];
+//! let len = radix::convert_base(&input, 256, 36, &mut digits).unwrap();
+//! assert_eq!(&digits[.. len], [0, 7, 4]);
+//! let symbol = |&x: &u8| char::from_digit(x.into(), 36).unwrap();
+//! assert_eq!(digits[.. len].iter().map(symbol).collect::<String>(), "074");
+//! let mut bytes = [0; 8];
+//! let len = radix::convert_base(&digits[.. len], 36, 256, &mut bytes).unwrap();
+//! assert_eq!(&bytes[.. len], input);
+//! ```
+//!
+//! [`BASE58`]: crate::BASE58
+//! [`Specification`]: crate::Specification
+
+use crate::arithmetic::{MAX_BIGINT_BUFFER, with_decode_chunks};
+use crate::bigint::BigUintView;
+use crate::{DecodeError, DecodeKind};
+
+/// Number of bits needed for the largest digit of `base`
+fn ceil_log2(base: u32) -> usize {
+	(32 - (base - 1).leading_zeros()) as usize
+}
+
+/// Returns an upper bound of the number of digits of [`convert_base`] for `len` digits
+///
+/// Returns `None` if `from` or `to` is not between 2 and 256, or if the bound overflows.
+#[must_use]
+pub fn convert_len(len: usize, from: u32, to: u32) -> Option<usize> {
+	if !(2..=256).contains(&from) || !(2..=256).contains(&to) {
+		return None;
+	}
+	// Leading zeros are kept one for one, while the other digits are converted. A digit of `to`
+	// holds at least `ilog2(to)` bits.
+	let value = len.checked_mul(ceil_log2(from))?.div_ceil(to.ilog2() as usize);
+	Some(core::cmp::max(len, value))
+}
+
+/// Converts the digits `input` in base `from` to digits in base `to` in `output`
+///
+/// Returns the number of digits written at the beginning of `output`. The value is computed on
+/// a stack buffer of 512 bytes, and on the heap for longer inputs with the `alloc` feature. Use
+/// [`convert_len`] to size `output`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - [`Base`] if `from` or `to` is not between 2 and 256,
+/// - [`Symbol`] if a digit of `input` is not smaller than `from`,
+/// - [`Overflow`] if `input` is too long without the `alloc` feature,
+/// - [`BufferTooSmall`] if `output` is too small.
+///
+/// [`Base`]: crate::DecodeKind::Base
+/// [`Symbol`]: crate::DecodeKind::Symbol
+/// [`Overflow`]: crate::DecodeKind::Overflow
+/// [`BufferTooSmall`]: crate::DecodeKind::BufferTooSmall
+pub fn convert_base(
+	input: &[u8],
+	from: u32,
+	to: u32,
+	output: &mut [u8],
+) -> Result<usize, DecodeError> {
+	let error = |position, kind| DecodeError {
+		position,
+		kind,
+	};
+	check!(error(0, DecodeKind::Base), (2..=256).contains(&from) && (2..=256).contains(&to));
+	if let Some(position) = input.iter().position(|&x| u32::from(x) >= from) {
+		return Err(error(position, DecodeKind::Symbol));
+	}
+	let zeros = input.iter().take_while(|&&x| x == 0).count();
+	with_decode_chunks::<MAX_BIGINT_BUFFER, _>(input, true, |chunks| {
+		let mut big = BigUintView::new(chunks);
+		for (position, &x) in input.iter().enumerate() {
+			big.mul_add(from, u32::from(x)).map_err(|()| error(position, DecodeKind::Overflow))?;
+		}
+		let mut written = 0;
+		while !big.is_zero() {
+			check!(error(0, DecodeKind::BufferTooSmall), written < output.len());
+			#[allow(clippy::cast_possible_truncation)] // smaller than `to`
+			let digit = big.div_mod(to) as u8;
+			output[written] = digit;
+			written += 1;
+		}
+		check!(error(0, DecodeKind::BufferTooSmall), output.len() - written >= zeros);
+		output[written..written + zeros].fill(0);
+		written += zeros;
+		output[..written].reverse();
+		Ok(written)
+	})
+}
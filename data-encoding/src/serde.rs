@@ -0,0 +1,189 @@
+//! Serde adapters for byte fields
+//!
+//! Each module of this module serializes bytes as a string of its encoding and can be used with
+//! `#[serde(with = "...")]`. Deserialization produces any type implementing `TryFrom<Vec<u8>>`,
+//! like `Vec<u8>`, `Box<[u8]>`, or `[u8; N]` which checks the length.
+//!
+//! For other positions, like the elements of a collection, [`SerdeEncoding`] wraps the bytes
+//! with a marker type implementing [`Base`]. Encodings which are not provided can be used by
+//! implementing [`Base`] for a new marker type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::serde::{Base58, SerdeEncoding};
+//!
+//! #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+//! struct Message {
+//!     #[serde(with = "data_encoding::serde::hex")]
+//!     id: [u8; 4],
+//!     #[serde(with = "data_encoding::serde::base64")]
+//!     body: Vec<u8>,
+//!     keys: Vec<SerdeEncoding<Base58>>,
+//! }
+//!
+//! let message = Message {
+//!     id: [0xde, 0xad, 0xbe, 0xef],
+//!     body: b"hello".to_vec(),
+//!     keys: vec![SerdeEncoding::new(vec![0, 1])],
+//! };
+//! let json = r#"{"id":"deadbeef","body":"aGVsbG8=","keys":["12"]}"#;
+//! assert_eq!(serde_json::to_string(&message).unwrap(), json);
+//! assert_eq!(serde_json::from_str::<Message>(json).unwrap(), message);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::Encoding;
+
+/// Encoding of a serde adapter
+pub trait Base {
+	/// Encoding used to serialize and deserialize the bytes.
+	const ENCODING: &'static Encoding;
+}
+
+/// Bytes serialized as a string with the encoding of `B`
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SerdeEncoding<B, T = Vec<u8>>(pub T, PhantomData<B>);
+
+impl<B, T> SerdeEncoding<B, T> {
+	/// Wraps `bytes`
+	pub const fn new(bytes: T) -> Self {
+		SerdeEncoding(bytes, PhantomData)
+	}
+
+	/// Returns the wrapped bytes
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<B, T> From<T> for SerdeEncoding<B, T> {
+	fn from(bytes: T) -> Self {
+		SerdeEncoding::new(bytes)
+	}
+}
+
+impl<B, T> core::ops::Deref for SerdeEncoding<B, T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<B, T> core::ops::DerefMut for SerdeEncoding<B, T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<B: Base, T: AsRef<[u8]>> Serialize for SerdeEncoding<B, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize::<B, T, S>(&self.0, serializer)
+	}
+}
+
+impl<'de, B: Base, T: TryFrom<Vec<u8>>> Deserialize<'de> for SerdeEncoding<B, T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize::<B, T, D>(deserializer).map(SerdeEncoding::new)
+	}
+}
+
+fn serialize<B: Base, T: AsRef<[u8]> + ?Sized, S: Serializer>(
+	bytes: &T,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&B::ENCODING.encode(bytes.as_ref()))
+}
+
+fn deserialize<'de, B: Base, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<T, D::Error> {
+	deserializer.deserialize_str(Visitor::<B, T>(PhantomData))
+}
+
+struct Visitor<B, T>(PhantomData<(B, T)>);
+
+impl<B: Base, T: TryFrom<Vec<u8>>> de::Visitor<'_> for Visitor<B, T> {
+	type Value = T;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "a string in {}", B::ENCODING.describe())
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+		self.visit_bytes(value.as_bytes())
+	}
+
+	fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+		let bytes = B::ENCODING.decode(value).map_err(E::custom)?;
+		let len = bytes.len();
+		T::try_from(bytes).map_err(|_| E::custom(format_args!("unexpected decoded length {len}")))
+	}
+}
+
+macro_rules! bases {
+	($($marker:ident $module:ident $encoding:ident;)*) => {
+		$(
+			#[doc = concat!(
+				"Marker for [`", stringify!($encoding), "`](crate::", stringify!($encoding), ")"
+			)]
+			#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+			pub struct $marker;
+
+			impl Base for $marker {
+				const ENCODING: &'static Encoding = &crate::$encoding;
+			}
+
+			#[doc = concat!(
+				"Adapter for [`", stringify!($encoding), "`](crate::", stringify!($encoding), ")"
+			)]
+			///
+			/// See the [module documentation](super) for an example.
+			pub mod $module {
+				use alloc::vec::Vec;
+
+				use ::serde::{Deserializer, Serializer};
+
+				/// Serializes `bytes` as a string
+				///
+				/// # Errors
+				///
+				/// Returns the errors of `serializer`.
+				pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(
+					bytes: &T,
+					serializer: S,
+				) -> Result<S::Ok, S::Error> {
+					super::serialize::<super::$marker, T, S>(bytes, serializer)
+				}
+
+				/// Deserializes bytes from a string
+				///
+				/// # Errors
+				///
+				/// Returns an error if the string is invalid or if the bytes do not convert to
+				/// `T`.
+				pub fn deserialize<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(
+					deserializer: D,
+				) -> Result<T, D::Error> {
+					super::deserialize::<super::$marker, T, D>(deserializer)
+				}
+			}
+		)*
+	};
+}
+
+bases! {
+	Hex hex HEXLOWER_PERMISSIVE;
+	Base32 base32 BASE32;
+	Base58 base58 BASE58;
+	Base64 base64 BASE64;
+	Base64Url base64url BASE64URL_NOPAD;
+}
@@ -0,0 +1,88 @@
+//! SHA-256 for the checksum of checked encodings.
+//!
+//! This is a straightforward implementation of FIPS 180-4, which is enough for the short inputs
+//! of Base58Check and avoids a dependency.
+
+const K: [u32; 64] = [
+	0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5, 0x3956_c25b, 0x59f1_11f1, 0x923f_82a4,
+	0xab1c_5ed5, 0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3, 0x72be_5d74, 0x80de_b1fe,
+	0x9bdc_06a7, 0xc19b_f174, 0xe49b_69c1, 0xefbe_4786, 0x0fc1_9dc6, 0x240c_a1cc, 0x2de9_2c6f,
+	0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da, 0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7,
+	0xc6e0_0bf3, 0xd5a7_9147, 0x06ca_6351, 0x1429_2967, 0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc,
+	0x5338_0d13, 0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85, 0xa2bf_e8a1, 0xa81a_664b,
+	0xc24b_8b70, 0xc76c_51a3, 0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070, 0x19a4_c116,
+	0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5, 0x391c_0cb3, 0x4ed8_aa4a, 0x5b9c_ca4f, 0x682e_6ff3,
+	0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208, 0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7,
+	0xc671_78f2,
+];
+
+const INIT: [u32; 8] = [
+	0x6a09_e667, 0xbb67_ae85, 0x3c6e_f372, 0xa54f_f53a, 0x510e_527f, 0x9b05_688c, 0x1f83_d9ab,
+	0x5be0_cd19,
+];
+
+/// Processes a 64-byte block.
+#[allow(clippy::many_single_char_names)] // names of the standard
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+	debug_assert_eq!(block.len(), 64);
+	let mut w = [0u32; 64];
+	for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+		*w = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+	}
+	for i in 16..64 {
+		let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+		let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+		w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+	}
+	let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+	for (&k, &w) in K.iter().zip(&w) {
+		let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+		let ch = (e & f) ^ (!e & g);
+		let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(k).wrapping_add(w);
+		let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+		let maj = (a & b) ^ (a & c) ^ (b & c);
+		let t2 = s0.wrapping_add(maj);
+		h = g;
+		g = f;
+		f = e;
+		e = d.wrapping_add(t1);
+		d = c;
+		c = b;
+		b = a;
+		a = t1.wrapping_add(t2);
+	}
+	for (x, y) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+		*x = x.wrapping_add(y);
+	}
+}
+
+/// Returns the SHA-256 digest of `input`.
+pub(crate) fn sha256(input: &[u8]) -> [u8; 32] {
+	let mut state = INIT;
+	let blocks = input.chunks_exact(64);
+	let rest = blocks.remainder();
+	for block in blocks {
+		compress(&mut state, block);
+	}
+	// The padding is a one bit, zeros, and the bit length on 8 bytes, in one or two blocks.
+	let mut last = [0u8; 128];
+	last[..rest.len()].copy_from_slice(rest);
+	last[rest.len()] = 0x80;
+	let len = if rest.len() < 56 { 64 } else { 128 };
+	let bits = (input.len() as u64).wrapping_mul(8);
+	last[len - 8..len].copy_from_slice(&bits.to_be_bytes());
+	for block in last[..len].chunks_exact(64) {
+		compress(&mut state, block);
+	}
+	let mut output = [0u8; 32];
+	for (bytes, x) in output.chunks_exact_mut(4).zip(state) {
+		bytes.copy_from_slice(&x.to_be_bytes());
+	}
+	output
+}
+
+/// Returns the first 4 bytes of the double SHA-256 digest of `input`.
+pub(crate) fn checksum(input: &[u8]) -> [u8; 4] {
+	let digest = sha256(&sha256(input));
+	[digest[0], digest[1], digest[2], digest[3]]
+}
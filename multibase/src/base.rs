@@ -5,7 +5,7 @@ use crate::impls::*;
 use alloc::{string::String, vec::Vec};
 
 macro_rules! build_base_enum {
-    ( $(#[$attr:meta] $code:expr => $base:ident,)* ) => {
+    ( $(#[$attr:meta] $code:expr, $name:expr => $base:ident,)* ) => {
         /// List of types currently supported in the multibase spec.
         ///
         /// Not all base types are supported by this library.
@@ -15,6 +15,9 @@ macro_rules! build_base_enum {
         }
 
         impl Base {
+            /// All supported base algorithms, in the order of the multibase table.
+            pub const ALL: &'static [Base] = &[ $( Self::$base, )* ];
+
             /// Convert a number to the matching base algorithm, or `Error` if no algorithm is matching.
             pub fn from_code(code: char) -> Result<Self> {
         	    match code {
@@ -30,6 +33,22 @@ macro_rules! build_base_enum {
                 }
             }
 
+            /// Convert a canonical multibase name (like `base58btc`) to the matching base algorithm,
+            /// or `Error` if no algorithm is matching.
+            pub fn from_name(name: &str) -> Result<Self> {
+                match name {
+                    $( $name => Ok(Self::$base), )*
+                    _ => Err(Error::UnknownBaseName(name.into())),
+                }
+            }
+
+            /// Get the canonical multibase name corresponding to the base algorithm.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $( Self::$base => $name, )*
+                }
+            }
+
             /// Encode the given byte slice to base string.
             pub fn encode<I: AsRef<[u8]>>(&self, input: I) -> Result<String> {
                 match self {
@@ -47,55 +66,130 @@ macro_rules! build_base_enum {
     }
 }
 
+impl Base {
+    /// Get the number of symbols and the characters that may appear in the encoded output.
+    ///
+    /// Returns `None` for [`Base::Identity`], whose output is the raw data.
+    pub(crate) fn alphabet(&self) -> Option<(usize, &'static str)> {
+        Some(match self {
+            Self::Identity => return None,
+            Self::Base2 => (2, "01"),
+            Self::Base8 => (8, "01234567"),
+            Self::Base10 => (10, "0123456789"),
+            Self::Base16Lower => (16, "0123456789abcdef"),
+            Self::Base16Upper => (16, "0123456789ABCDEF"),
+            Self::Base32Lower => (32, "abcdefghijklmnopqrstuvwxyz234567"),
+            Self::Base32Upper => (32, "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+            Self::Base32PadLower => (32, "abcdefghijklmnopqrstuvwxyz234567="),
+            Self::Base32PadUpper => (32, "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567="),
+            Self::Base32HexLower => (32, "0123456789abcdefghijklmnopqrstuv"),
+            Self::Base32HexUpper => (32, "0123456789ABCDEFGHIJKLMNOPQRSTUV"),
+            Self::Base32HexPadLower => (32, "0123456789abcdefghijklmnopqrstuv="),
+            Self::Base32HexPadUpper => (32, "0123456789ABCDEFGHIJKLMNOPQRSTUV="),
+            Self::Base32Z => (32, "ybndrfg8ejkmcpqxot1uwisza345h769"),
+            Self::Base36Lower => (36, "0123456789abcdefghijklmnopqrstuvwxyz"),
+            Self::Base36Upper => (36, "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            Self::Base45 => (45, "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:"),
+            Self::Base58Flickr => (58, crate::encoding::BASE58_FLICKR),
+            Self::Base58Btc => (58, crate::encoding::BASE58_BITCOIN),
+            Self::Base64 => (64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+            Self::Base64Pad => (64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/="),
+            Self::Base64Url => (64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"),
+            Self::Base64UrlPad => (64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_="),
+            Self::Base256Emoji => (256, ""),
+        })
+    }
+
+    /// Whether the encoded output never contains any of the `excluding` characters.
+    pub(crate) fn avoids(&self, excluding: &[char]) -> bool {
+        if *self == Self::Base256Emoji {
+            return !base256emoji::ALPHABET.iter().any(|c| excluding.contains(c));
+        }
+        match self.alphabet() {
+            Some((_, alphabet)) => !alphabet.chars().any(|c| excluding.contains(&c)),
+            None => false,
+        }
+    }
+}
+
+impl core::str::FromStr for Base {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name)
+    }
+}
+
+impl core::fmt::Display for Base {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Base {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Base {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name).map_err(serde::de::Error::custom)
+    }
+}
+
 build_base_enum! {
     /// 8-bit binary (encoder and decoder keeps data unmodified).
-    '\x00' => Identity,
+    '\x00', "identity" => Identity,
     /// Base2 (alphabet: 01).
-    '0' => Base2,
+    '0', "base2" => Base2,
     /// Base8 (alphabet: 01234567).
-    '7' => Base8,
+    '7', "base8" => Base8,
     /// Base10 (alphabet: 0123456789).
-    '9' => Base10,
+    '9', "base10" => Base10,
     /// Base16 lower hexadecimal (alphabet: 0123456789abcdef).
-    'f' => Base16Lower,
+    'f', "base16" => Base16Lower,
     /// Base16 upper hexadecimal (alphabet: 0123456789ABCDEF).
-    'F' => Base16Upper,
+    'F', "base16upper" => Base16Upper,
      /// Base32, rfc4648 no padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    'b' => Base32Lower,
+    'b', "base32" => Base32Lower,
     /// Base32, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    'B' => Base32Upper,
+    'B', "base32upper" => Base32Upper,
     /// Base32, rfc4648 with padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    'c' => Base32PadLower,
+    'c', "base32pad" => Base32PadLower,
     /// Base32, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    'C' => Base32PadUpper,
+    'C', "base32padupper" => Base32PadUpper,
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    'v' => Base32HexLower,
+    'v', "base32hex" => Base32HexLower,
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    'V' => Base32HexUpper,
+    'V', "base32hexupper" => Base32HexUpper,
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    't' => Base32HexPadLower,
+    't', "base32hexpad" => Base32HexPadLower,
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    'T' => Base32HexPadUpper,
+    'T', "base32hexpadupper" => Base32HexPadUpper,
     /// z-base-32 (used by Tahoe-LAFS) (alphabet: ybndrfg8ejkmcpqxot1uwisza345h769).
-    'h' => Base32Z,
+    'h', "base32z" => Base32Z,
     /// Base36, [0-9a-z] no padding (alphabet: 0123456789abcdefghijklmnopqrstuvwxyz).
-    'k' => Base36Lower,
+    'k', "base36" => Base36Lower,
     /// Base36, [0-9A-Z] no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ).
-    'K' => Base36Upper,
+    'K', "base36upper" => Base36Upper,
     /// Base45, rfc9285 (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:).
-    'R' => Base45,
+    'R', "base45" => Base45,
     /// Base58 flicker (alphabet: 123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ).
-    'Z' => Base58Flickr,
+    'Z', "base58flickr" => Base58Flickr,
     /// Base58 bitcoin (alphabet: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz).
-    'z' => Base58Btc,
+    'z', "base58btc" => Base58Btc,
     /// Base64, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    'm' => Base64,
+    'm', "base64" => Base64,
     /// Base64, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    'M' => Base64Pad,
+    'M', "base64pad" => Base64Pad,
     /// Base64 url, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    'u' => Base64Url,
+    'u', "base64url" => Base64Url,
     /// Base64 url, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    'U' => Base64UrlPad,
+    'U', "base64urlpad" => Base64UrlPad,
 /// Base256Emoji (alphabet: 🚀🪐☄🛰🌌🌑🌒🌓🌔🌕🌖🌗🌘🌍🌏🌎🐉☀💻🖥💾💿😂❤😍🤣😊🙏💕😭😘👍😅👏😁🔥🥰💔💖💙😢🤔😆🙄💪😉☺👌🤗💜😔😎😇🌹🤦🎉💞✌✨🤷😱😌🌸🙌😋💗💚😏💛🙂💓🤩😄😀🖤😃💯🙈👇🎶😒🤭❣😜💋👀😪😑💥🙋😞😩😡🤪👊🥳😥🤤👉💃😳✋😚😝😴🌟😬🙃🍀🌷😻😓⭐✅🥺🌈😈🤘💦✔😣🏃💐☹🎊💘😠☝😕🌺🎂🌻😐🖕💝🙊😹🗣💫💀👑🎵🤞😛🔴😤🌼😫⚽🤙☕🏆🤫👈😮🙆🍻🍃🐶💁😲🌿🧡🎁⚡🌞🎈❌✊👋😰🤨😶🤝🚶💰🍓💢🤟🙁🚨💨🤬✈🎀🍺🤓😙💟🌱😖👶🥴▶➡❓💎💸⬇😨🌚🦋😷🕺⚠🙅😟😵👎🤲🤠🤧📌🔵💅🧐🐾🍒😗🤑🌊🤯🐷☎💧😯💆👆🎤🙇🍑❄🌴💣🐸💌📍🥀🤢👅💡💩👐📸👻🤐🤮🎼🥵🚩🍎🍊👼💍📣🥂)
-    '🚀' => Base256Emoji,
+    '🚀', "base256emoji" => Base256Emoji,
 }
@@ -1,5 +1,8 @@
 use core::fmt;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
 /// Type alias to use this library's [`Error`] type in a `Result`.
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -8,6 +11,8 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum Error {
     /// Unknown base code.
     UnknownBase(char),
+    /// Unknown base name.
+    UnknownBaseName(String),
     /// Invalid string.
     InvalidBaseString,
 }
@@ -16,6 +21,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::UnknownBase(code) => write!(f, "Unknown base code: {}", code),
+            Error::UnknownBaseName(name) => write!(f, "Unknown base name: {}", name),
             Error::InvalidBaseString => write!(f, "Invalid base string"),
         }
     }
@@ -39,6 +39,73 @@ pub fn decode<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
     Ok((base, decoded))
 }
 
+/// Decode the base string, also returning its base code.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_with_code};
+///
+/// assert_eq!(
+///     decode_with_code("zCn8eVZg").unwrap(),
+///     ('z', Base::Base58Btc, b"hello".to_vec())
+/// );
+/// ```
+pub fn decode_with_code<T: AsRef<str>>(input: T) -> Result<(char, Base, Vec<u8>)> {
+    let input = input.as_ref();
+    let code = input.chars().next().ok_or(Error::InvalidBaseString)?;
+    let (base, decoded) = decode(input)?;
+    Ok((code, base, decoded))
+}
+
+/// Decode the base string, ignoring ASCII whitespace in the payload.
+///
+/// This accepts strings that gained stray spaces or line breaks, for example when copied from
+/// logs. The base code must still be the first character. The payload of [`Base::Identity`] is
+/// raw data, so whitespace is kept there.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_lenient};
+///
+/// assert_eq!(
+///     decode_lenient("zCn8 eV\nZg").unwrap(),
+///     (Base::Base58Btc, b"hello".to_vec())
+/// );
+/// ```
+pub fn decode_lenient<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
+    let input = input.as_ref();
+    let code = input.chars().next().ok_or(Error::InvalidBaseString)?;
+    let base = Base::from_code(code)?;
+    let payload = &input[code.len_utf8()..];
+    if base == Base::Identity {
+        return Ok((base, base.decode(payload)?));
+    }
+    let payload: String = payload.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    let decoded = base.decode(&payload)?;
+    Ok((base, decoded))
+}
+
+/// Decode the base string with a known base.
+///
+/// Unlike [`decode`], `input` must not start with the base code. This is useful for fields
+/// whose base is fixed by the protocol and whose producers omit the redundant prefix.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_with_base};
+///
+/// assert_eq!(
+///     decode_with_base(Base::Base58Btc, "Cn8eVZg").unwrap(),
+///     b"hello".to_vec()
+/// );
+/// ```
+pub fn decode_with_base<T: AsRef<str>>(base: Base, input: T) -> Result<Vec<u8>> {
+    base.decode(input.as_ref())
+}
+
 /// Encode with the given byte slice to base string.
 ///
 /// # Examples
@@ -54,6 +121,59 @@ pub fn encode<T: AsRef<[u8]>>(base: Base, input: T) -> Result<String> {
     encoded.insert(0, base.code());
     Ok(encoded)
 }
+
+/// Find the most compact base whose output avoids the given characters.
+///
+/// Bases are ranked by the number of output characters per input byte, which only depends on
+/// the alphabet size. Among bases of the same size, the first one in [`Base::ALL`] wins, so
+/// unpadded variants are preferred. [`Base::Identity`] is never returned since its output is
+/// the raw data.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, most_compact};
+///
+/// assert_eq!(most_compact(&['0', '1', '🚀']), Some(Base::Base32Lower));
+/// ```
+pub fn most_compact(excluding: &[char]) -> Option<Base> {
+    let mut best: Option<(usize, Base)> = None;
+    for &base in Base::ALL {
+        let size = match base.alphabet() {
+            Some((size, _)) if base.avoids(excluding) => size,
+            _ => continue,
+        };
+        match best {
+            Some((best_size, _)) if best_size >= size => {}
+            _ => best = Some((size, base)),
+        }
+    }
+    best.map(|(_, base)| base)
+}
+
+/// Encode and decode a fixed test vector with every base, as a diagnostic.
+///
+/// The vector has leading zero bytes and multi-byte UTF-8 characters (so that it is also valid
+/// for [`Base::Identity`]). Returns the first base whose roundtrip fails, with the error, or
+/// [`Error::InvalidBaseString`] if the decoded data differs.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(multibase::self_test(), Ok(()));
+/// ```
+pub fn self_test() -> core::result::Result<(), (Base, Error)> {
+    const VECTOR: &[u8] = "\0\0multibase \u{2713} \u{1f680}\u{7f}".as_bytes();
+    for &base in Base::ALL {
+        let encoded = encode(base, VECTOR).map_err(|e| (base, e))?;
+        let (decoded_base, decoded) = decode(&encoded).map_err(|e| (base, e))?;
+        if decoded_base != base || decoded != VECTOR {
+            return Err((base, Error::InvalidBaseString));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod debug_tests {
     use super::*;
@@ -1,4 +1,7 @@
-use multibase::{decode, encode, Base, Base::*};
+use multibase::{
+    decode, decode_lenient, decode_with_base, decode_with_code, encode, most_compact, self_test,
+    Base, Base::*,
+};
 
 fn encode_decode_assert(input: &[u8], test_cases: Vec<(Base, &str)>) {
     for (base, output) in test_cases {
@@ -34,6 +37,38 @@ fn test_round_trip() {
     }
 }
 
+#[test]
+fn test_decode_with_base() {
+    assert_eq!(
+        decode_with_base(Base58Btc, "7paNL19xttacUY").unwrap(),
+        b"yes mani !".to_vec()
+    );
+    assert_eq!(
+        decode_with_base(Base58Btc, "17paNL19xttacUY").unwrap(),
+        b"\x00yes mani !".to_vec()
+    );
+    assert_eq!(decode_with_base(Base58Btc, "").unwrap(), Vec::<u8>::new());
+    // The prefix is not stripped, `z` is a regular base58btc symbol.
+    assert_ne!(
+        decode_with_base(Base58Btc, "z7paNL19xttacUY").unwrap(),
+        b"yes mani !".to_vec()
+    );
+    assert!(decode_with_base(Base58Btc, "0OIl").is_err());
+}
+
+#[test]
+fn test_decode_with_code() {
+    let input = b"yes mani !";
+    for base in [Base2, Base16Upper, Base32Z, Base58Btc, Base64UrlPad, Base256Emoji] {
+        let encoded = encode(base, input).unwrap();
+        let code = encoded.chars().next().unwrap();
+        assert_eq!(code, base.code());
+        assert_eq!(decode_with_code(&encoded).unwrap(), (code, base, input.to_vec()));
+    }
+    assert!(decode_with_code("").is_err());
+    assert!(decode_with_code("Lfoo").is_err());
+}
+
 #[test]
 fn test_basic() {
     let input = b"yes mani !";
@@ -158,3 +193,92 @@ fn case_insensitivity() {
         assert_eq!(decode(output).unwrap(), (base, input.to_vec()));
     }
 }
+
+#[test]
+fn base58_flickr() {
+    assert_eq!(Base::from_code('Z').unwrap(), Base58Flickr);
+    assert_eq!(Base::from_code('z').unwrap(), Base58Btc);
+    let flickr = encode(Base58Flickr, b"hello").unwrap();
+    let btc = encode(Base58Btc, b"hello").unwrap();
+    assert_eq!(flickr, "ZcM8DuyF");
+    assert_ne!(flickr[1..], btc[1..]);
+    assert_eq!(decode(&flickr).unwrap(), (Base58Flickr, b"hello".to_vec()));
+}
+
+#[test]
+fn lenient_whitespace() {
+    let expected = (Base58Btc, b"hello world".to_vec());
+    let encoded = encode(Base58Btc, b"hello world").unwrap();
+    assert_eq!(encoded, "zStV1DL6CwTryKyV");
+    assert_eq!(decode_lenient(&encoded).unwrap(), expected);
+    assert_eq!(decode_lenient("zStV1 DL6C\twTry\r\nKyV ").unwrap(), expected);
+    assert!(decode("zStV1 DL6CwTryKyV").is_err());
+    assert_eq!(
+        decode_lenient("MZm9v YmFy").unwrap(),
+        (Base64Pad, b"foobar".to_vec())
+    );
+    assert_eq!(
+        decode_lenient("\x00a b").unwrap(),
+        (Identity, b"a b".to_vec())
+    );
+    assert!(decode_lenient(" zStV1DL6CwTryKyV").is_err());
+}
+
+#[test]
+fn base64url_padding() {
+    assert_eq!(Base::from_code('u').unwrap(), Base64Url);
+    assert_eq!(Base::from_code('U').unwrap(), Base64UrlPad);
+    let input = b"\xfb\xff";
+    assert_eq!(encode(Base64Url, input).unwrap(), "u-_8");
+    assert_eq!(encode(Base64UrlPad, input).unwrap(), "U-_8=");
+    let (base, data) = decode("u-_8").unwrap();
+    assert_eq!((base, &data[..]), (Base64Url, &input[..]));
+    assert_eq!(encode(base, data).unwrap(), "u-_8");
+    let (base, data) = decode("U-_8=").unwrap();
+    assert_eq!((base, &data[..]), (Base64UrlPad, &input[..]));
+    assert_eq!(encode(base, data).unwrap(), "U-_8=");
+    assert!(decode("u-_8=").is_err());
+    assert!(decode("U-_8").is_err());
+}
+
+#[test]
+fn base_names() {
+    assert_eq!(Base58Btc.name(), "base58btc");
+    assert_eq!(Base::from_name("base32hexpadupper").unwrap(), Base32HexPadUpper);
+    assert_eq!("base256emoji".parse::<Base>().unwrap(), Base256Emoji);
+    assert_eq!(Base64UrlPad.to_string(), "base64urlpad");
+    assert_eq!(
+        "base999".parse::<Base>(),
+        Err(multibase::Error::UnknownBaseName("base999".into()))
+    );
+}
+
+#[test]
+fn most_compact_excluding() {
+    let vowels = ['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U'];
+    assert_eq!(most_compact(&vowels), Some(Base256Emoji));
+    assert_eq!(most_compact(&['a', 'e', 'i', 'o', 'u', '🚀']), Some(Base45));
+    let mut excluding = vowels.to_vec();
+    excluding.push('🚀');
+    assert_eq!(most_compact(&excluding), Some(Base10));
+    assert_eq!(most_compact(&['0', '1', '2', '🚀']), None);
+    assert_eq!(Base::ALL.len(), 25);
+    assert!(Base::ALL.iter().all(|&base| Base::from_code(base.code()) == Ok(base)));
+}
+
+#[test]
+fn self_test_passes() {
+    assert_eq!(self_test(), Ok(()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_base() {
+    for base in [Identity, Base16Lower, Base36Upper, Base45, Base58Flickr, Base256Emoji] {
+        let json = serde_json::to_string(&base).unwrap();
+        assert_eq!(json, format!("\"{}\"", base.name()));
+        assert_eq!(serde_json::from_str::<Base>(&json).unwrap(), base);
+    }
+    let error = serde_json::from_str::<Base>("\"base999\"").unwrap_err();
+    assert!(error.to_string().contains("Unknown base name: base999"));
+}